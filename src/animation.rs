@@ -1,7 +1,8 @@
 use crate::{sys::*, *};
 use derivative::Derivative;
+use std::ops::Mul;
 
-#[derive(Derivative)]
+#[derive(Clone, Derivative)]
 #[derivative(Debug)]
 pub struct MeshMorphKey {
     pub time: f64,
@@ -22,7 +23,7 @@ impl From<&aiMeshMorphKey> for MeshMorphKey {
     }
 }
 
-#[derive(Derivative)]
+#[derive(Clone, Derivative)]
 #[derivative(Debug)]
 pub struct MeshMorphAnim {
     pub keys: Vec<MeshMorphKey>,
@@ -38,8 +39,113 @@ impl From<&aiMeshMorphAnim> for MeshMorphAnim {
     }
 }
 
+impl MeshMorphAnim {
+    /// Evaluates the blend-shape weights at `time`, given in ticks.
+    ///
+    /// The result holds one weight per anim mesh index referenced by the keys,
+    /// linearly interpolated between the surrounding keys. Times outside of the
+    /// keyed range are clamped to the first or last key.
+    pub fn sample(&self, time: f64) -> Vec<f32> {
+        let len = self
+            .keys
+            .iter()
+            .flat_map(|key| key.values.iter())
+            .max()
+            .map_or(0, |&max| max as usize + 1);
+        let mut weights = vec![0.0; len];
+
+        let next = self.keys.partition_point(|key| key.time <= time);
+        let (from, to, factor) = match next {
+            0 => (self.keys.first(), None, 0.0),
+            next if next == self.keys.len() => (self.keys.last(), None, 0.0),
+            next => {
+                let (from, to) = (&self.keys[next - 1], &self.keys[next]);
+                let factor = (time - from.time) / (to.time - from.time);
+                (Some(from), Some(to), factor)
+            }
+        };
+
+        if let Some(from) = from {
+            from.add_weights(&mut weights, 1.0 - factor);
+        }
+        if let Some(to) = to {
+            to.add_weights(&mut weights, factor);
+        }
+
+        weights
+    }
+
+    /// Keeps the keys between `start` and `end`, shifted to start at 0. Keys
+    /// are inserted at both ends so that the weights are kept there.
+    pub fn trim(&self, start: f64, end: f64) -> MeshMorphAnim {
+        if self.keys.is_empty() {
+            return self.clone();
+        }
+
+        let mut keys = vec![self.key_at(start, 0.0)];
+        keys.extend(
+            self.keys
+                .iter()
+                .filter(|key| key.time > start && key.time < end)
+                .map(|key| MeshMorphKey {
+                    time: key.time - start,
+                    ..key.clone()
+                }),
+        );
+        if end > start {
+            keys.push(self.key_at(end, end - start));
+        }
+
+        MeshMorphAnim {
+            keys,
+            name: self.name.clone(),
+        }
+    }
+
+    /// Replaces the keys by samples taken at `times`, given in ticks.
+    pub fn resample(&self, times: &[f64]) -> MeshMorphAnim {
+        if self.keys.is_empty() {
+            return self.clone();
+        }
+
+        MeshMorphAnim {
+            keys: times.iter().map(|&time| self.key_at(time, time)).collect(),
+            name: self.name.clone(),
+        }
+    }
+
+    fn key_at(&self, time: f64, key_time: f64) -> MeshMorphKey {
+        let weights = self.sample(time);
+
+        MeshMorphKey {
+            time: key_time,
+            values: (0..weights.len() as u32).collect(),
+            weights: weights.into_iter().map(f64::from).collect(),
+        }
+    }
+}
+
+impl MeshMorphKey {
+    fn add_weights(&self, weights: &mut [f32], factor: f64) {
+        for (&value, &weight) in self.values.iter().zip(&self.weights) {
+            weights[value as usize] += (weight * factor) as f32;
+        }
+    }
+}
+
+/// Blend-shape weights of a single morph channel, see
+/// [`Animation::sample_morph_weights`].
 #[derive(Derivative)]
 #[derivative(Debug)]
+pub struct MorphWeights {
+    /// Name of the mesh(es) affected by the channel.
+    pub name: String,
+    /// One weight per entry of [`Mesh::anim_meshes`](crate::mesh::Mesh::anim_meshes).
+    pub weights: Vec<f32>,
+}
+
+#[derive(Copy, Clone, Derivative)]
+#[derivative(Debug)]
 pub struct VectorKey {
     pub time: f64,
     pub value: Vector3D,
@@ -91,7 +197,146 @@ impl From<&aiQuaternion> for Quaternion {
     }
 }
 
-#[derive(Derivative)]
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Quaternion {
+    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Self { w, x, y, z }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// The inverse rotation of a unit quaternion.
+    pub fn conjugate(&self) -> Quaternion {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    pub fn dot(&self, rhs: &Quaternion) -> f32 {
+        self.w * rhs.w + self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn normalize(&self) -> Quaternion {
+        let length = self.dot(self).sqrt();
+        if length == 0.0 {
+            return *self;
+        }
+
+        Self::new(
+            self.w / length,
+            self.x / length,
+            self.y / length,
+            self.z / length,
+        )
+    }
+
+    /// Extracts the rotation from the upper 3x3 part of `matrix`, which must
+    /// not contain any scaling.
+    pub fn from_matrix(matrix: &Matrix4x4) -> Quaternion {
+        let trace = matrix.a1 + matrix.b2 + matrix.c3;
+
+        if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new(
+                0.25 * s,
+                (matrix.c2 - matrix.b3) / s,
+                (matrix.a3 - matrix.c1) / s,
+                (matrix.b1 - matrix.a2) / s,
+            )
+        } else if matrix.a1 > matrix.b2 && matrix.a1 > matrix.c3 {
+            let s = (1.0 + matrix.a1 - matrix.b2 - matrix.c3).sqrt() * 2.0;
+            Self::new(
+                (matrix.c2 - matrix.b3) / s,
+                0.25 * s,
+                (matrix.a2 + matrix.b1) / s,
+                (matrix.c1 + matrix.a3) / s,
+            )
+        } else if matrix.b2 > matrix.c3 {
+            let s = (1.0 + matrix.b2 - matrix.a1 - matrix.c3).sqrt() * 2.0;
+            Self::new(
+                (matrix.a3 - matrix.c1) / s,
+                (matrix.a2 + matrix.b1) / s,
+                0.25 * s,
+                (matrix.b3 + matrix.c2) / s,
+            )
+        } else {
+            let s = (1.0 + matrix.c3 - matrix.a1 - matrix.b2).sqrt() * 2.0;
+            Self::new(
+                (matrix.b1 - matrix.a2) / s,
+                (matrix.c1 + matrix.a3) / s,
+                (matrix.b3 + matrix.c2) / s,
+                0.25 * s,
+            )
+        }
+    }
+
+    /// Rotation matrix of a unit quaternion.
+    pub fn to_matrix(&self) -> Matrix4x4 {
+        let Quaternion { w, x, y, z } = *self;
+
+        Matrix4x4 {
+            a1: 1.0 - 2.0 * (y * y + z * z),
+            a2: 2.0 * (x * y - z * w),
+            a3: 2.0 * (x * z + y * w),
+            b1: 2.0 * (x * y + z * w),
+            b2: 1.0 - 2.0 * (x * x + z * z),
+            b3: 2.0 * (y * z - x * w),
+            c1: 2.0 * (x * z - y * w),
+            c2: 2.0 * (y * z + x * w),
+            c3: 1.0 - 2.0 * (x * x + y * y),
+            ..Matrix4x4::identity()
+        }
+    }
+
+    /// Spherical linear interpolation along the shortest path, `factor` 0
+    /// returns `self` and 1 returns `to`.
+    pub fn slerp(&self, to: &Quaternion, factor: f32) -> Quaternion {
+        let mut cosom = self.dot(to);
+        let mut end = *to;
+        if cosom < 0.0 {
+            cosom = -cosom;
+            end = Self::new(-end.w, -end.x, -end.y, -end.z);
+        }
+
+        let (sclp, sclq) = if 1.0 - cosom > 0.0001 {
+            let omega = cosom.acos();
+            let sinom = omega.sin();
+            (
+                ((1.0 - factor) * omega).sin() / sinom,
+                (factor * omega).sin() / sinom,
+            )
+        } else {
+            (1.0 - factor, factor)
+        };
+
+        Self::new(
+            sclp * self.w + sclq * end.w,
+            sclp * self.x + sclq * end.x,
+            sclp * self.y + sclq * end.y,
+            sclp * self.z + sclq * end.z,
+        )
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, rhs: Quaternion) -> Self::Output {
+        Self::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y + self.y * rhs.w + self.z * rhs.x - self.x * rhs.z,
+            self.w * rhs.z + self.z * rhs.w + self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+}
+
+#[derive(Clone, Derivative)]
 #[derivative(Debug)]
 pub struct NodeAnim {
     pub name: String,
@@ -115,7 +360,220 @@ impl From<&aiNodeAnim> for NodeAnim {
     }
 }
 
-#[derive(Derivative)]
+impl NodeAnim {
+    /// Position at `time`, given in ticks, linearly interpolated between the
+    /// surrounding keys and clamped to the first or last key.
+    pub fn sample_position(&self, time: f64) -> Option<Vector3D> {
+        sample_keys(&self.position_keys, time).map(|key| key.value)
+    }
+
+    /// Rotation at `time`, given in ticks, spherically interpolated between the
+    /// surrounding keys and clamped to the first or last key.
+    pub fn sample_rotation(&self, time: f64) -> Option<Quaternion> {
+        sample_keys(&self.rotation_keys, time).map(|key| key.value)
+    }
+
+    /// Scaling at `time`, given in ticks, linearly interpolated between the
+    /// surrounding keys and clamped to the first or last key.
+    pub fn sample_scaling(&self, time: f64) -> Option<Vector3D> {
+        sample_keys(&self.scaling_keys, time).map(|key| key.value)
+    }
+
+    /// Keeps the keys between `start` and `end`, shifted to start at 0. Keys
+    /// are inserted at both ends so that the channel keeps its pose there.
+    pub fn trim(&self, start: f64, end: f64) -> NodeAnim {
+        NodeAnim {
+            position_keys: trim_keys(&self.position_keys, start, end),
+            rotation_keys: trim_keys(&self.rotation_keys, start, end),
+            scaling_keys: trim_keys(&self.scaling_keys, start, end),
+            name: self.name.clone(),
+            post_state: self.post_state,
+            pre_state: self.pre_state,
+        }
+    }
+
+    /// Replaces the keys by samples taken at `times`, given in ticks.
+    pub fn resample(&self, times: &[f64]) -> NodeAnim {
+        NodeAnim {
+            position_keys: resample_keys(&self.position_keys, times),
+            rotation_keys: resample_keys(&self.rotation_keys, times),
+            scaling_keys: resample_keys(&self.scaling_keys, times),
+            name: self.name.clone(),
+            post_state: self.post_state,
+            pre_state: self.pre_state,
+        }
+    }
+
+    /// Drops every key that can be interpolated from the remaining keys
+    /// within `tolerance`.
+    pub fn reduce_keys(&self, tolerance: &KeyTolerance) -> NodeAnim {
+        NodeAnim {
+            position_keys: reduce_keys(&self.position_keys, tolerance.position),
+            rotation_keys: reduce_keys(&self.rotation_keys, tolerance.rotation),
+            scaling_keys: reduce_keys(&self.scaling_keys, tolerance.scaling),
+            name: self.name.clone(),
+            post_state: self.post_state,
+            pre_state: self.pre_state,
+        }
+    }
+}
+
+/// Largest error allowed when removing keys, see [`Animation::reduce_keys`].
+#[derive(Clone, Copy, Derivative)]
+#[derivative(Debug)]
+pub struct KeyTolerance {
+    /// Distance between the original and the interpolated position.
+    pub position: f32,
+    /// Angle in radians between the original and the interpolated rotation.
+    pub rotation: f32,
+    /// Distance between the original and the interpolated scaling.
+    pub scaling: f32,
+}
+
+impl Default for KeyTolerance {
+    fn default() -> Self {
+        Self {
+            position: 1e-4,
+            rotation: 1e-4,
+            scaling: 1e-4,
+        }
+    }
+}
+
+trait Keyframe: Copy {
+    fn time(&self) -> f64;
+
+    fn with_time(&self, time: f64) -> Self;
+
+    /// Key at `time` between `self` and `to`.
+    fn interpolate(&self, to: &Self, time: f64) -> Self;
+
+    fn distance(&self, other: &Self) -> f32;
+
+    fn factor(&self, to: &Self, time: f64) -> f32 {
+        let span = to.time() - self.time();
+        if span > 0.0 {
+            ((time - self.time()) / span) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Keyframe for VectorKey {
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn with_time(&self, time: f64) -> Self {
+        Self { time, ..*self }
+    }
+
+    fn interpolate(&self, to: &Self, time: f64) -> Self {
+        Self {
+            time,
+            value: self.value + (to.value - self.value) * self.factor(to, time),
+        }
+    }
+
+    fn distance(&self, other: &Self) -> f32 {
+        (self.value - other.value).length()
+    }
+}
+
+impl Keyframe for QuatKey {
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn with_time(&self, time: f64) -> Self {
+        Self { time, ..*self }
+    }
+
+    fn interpolate(&self, to: &Self, time: f64) -> Self {
+        Self {
+            time,
+            value: self
+                .value
+                .slerp(&to.value, self.factor(to, time))
+                .normalize(),
+        }
+    }
+
+    fn distance(&self, other: &Self) -> f32 {
+        // acos of the dot product is too imprecise for small angles
+        let delta = self.value * other.value.conjugate();
+        let sin = (delta.x * delta.x + delta.y * delta.y + delta.z * delta.z).sqrt();
+
+        // q and -q describe the same rotation
+        2.0 * sin.atan2(delta.w.abs())
+    }
+}
+
+fn sample_keys<K: Keyframe>(keys: &[K], time: f64) -> Option<K> {
+    let next = keys.partition_point(|key| key.time() <= time);
+    match next {
+        0 => keys.first().map(|key| key.with_time(time)),
+        next if next == keys.len() => keys.last().map(|key| key.with_time(time)),
+        next => Some(keys[next - 1].interpolate(&keys[next], time)),
+    }
+}
+
+fn trim_keys<K: Keyframe>(keys: &[K], start: f64, end: f64) -> Vec<K> {
+    let (first, last) = match (sample_keys(keys, start), sample_keys(keys, end)) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return vec![],
+    };
+
+    let mut trimmed = vec![first.with_time(0.0)];
+    trimmed.extend(
+        keys.iter()
+            .filter(|key| key.time() > start && key.time() < end)
+            .map(|key| key.with_time(key.time() - start)),
+    );
+    if end > start {
+        trimmed.push(last.with_time(end - start));
+    }
+
+    trimmed
+}
+
+fn resample_keys<K: Keyframe>(keys: &[K], times: &[f64]) -> Vec<K> {
+    times
+        .iter()
+        .filter_map(|&time| sample_keys(keys, time))
+        .collect()
+}
+
+fn reduce_keys<K: Keyframe>(keys: &[K], tolerance: f32) -> Vec<K> {
+    let (first, last) = match keys {
+        [first, .., last] => (*first, *last),
+        _ => return keys.to_vec(),
+    };
+
+    let mut reduced = vec![first];
+    let mut anchor = 0;
+    for index in 1..keys.len() - 1 {
+        // every key dropped since the anchor has to stay within tolerance
+        let next = &keys[index + 1];
+        let fits = keys[anchor + 1..=index]
+            .iter()
+            .all(|key| keys[anchor].interpolate(next, key.time()).distance(key) <= tolerance);
+
+        if !fits {
+            reduced.push(keys[index]);
+            anchor = index;
+        }
+    }
+
+    if reduced.len() > 1 || first.distance(&last) > tolerance {
+        reduced.push(last);
+    }
+
+    reduced
+}
+
+#[derive(Clone, Derivative)]
 #[derivative(Debug)]
 pub struct MeshAnim {
     pub name: String,
@@ -131,7 +589,42 @@ impl From<&aiMeshAnim> for MeshAnim {
     }
 }
 
-#[derive(Derivative)]
+impl MeshAnim {
+    /// Returns the index of the anim mesh shown at `time`, given in ticks.
+    ///
+    /// Vertex animation keys are not interpolated, each key stays active until
+    /// the next one starts.
+    pub fn sample(&self, time: f64) -> Option<u32> {
+        let next = self.keys.partition_point(|key| key.time <= time);
+        self.keys.get(next.saturating_sub(1)).map(|key| key.value)
+    }
+
+    /// Keeps the keys between `start` and `end`, shifted to start at 0, with a
+    /// key at 0 for the anim mesh that is shown at `start`.
+    pub fn trim(&self, start: f64, end: f64) -> MeshAnim {
+        let mut keys: Vec<MeshKey> = self
+            .sample(start)
+            .map(|value| MeshKey { time: 0.0, value })
+            .into_iter()
+            .collect();
+        keys.extend(
+            self.keys
+                .iter()
+                .filter(|key| key.time > start && key.time <= end)
+                .map(|key| MeshKey {
+                    time: key.time - start,
+                    value: key.value,
+                }),
+        );
+
+        MeshAnim {
+            name: self.name.clone(),
+            keys,
+        }
+    }
+}
+
+#[derive(Clone, Derivative)]
 #[derivative(Debug)]
 pub struct MeshKey {
     pub time: f64,
@@ -147,7 +640,7 @@ impl From<&aiMeshKey> for MeshKey {
     }
 }
 
-#[derive(Default, Derivative)]
+#[derive(Clone, Default, Derivative)]
 #[derivative(Debug)]
 pub struct Animation {
     pub name: String,
@@ -158,28 +651,360 @@ pub struct Animation {
     pub ticks_per_second: f64,
 }
 
-impl From<&aiAnimation> for Animation {
-    fn from(animation: &aiAnimation) -> Self {
-        Self {
+impl TryFrom<&aiAnimation> for Animation {
+    type Error = RussimpError;
+
+    fn try_from(animation: &aiAnimation) -> Russult<Self> {
+        Ok(Self {
             name: animation.mName.into(),
-            channels: utils::get_vec_from_raw(animation.mChannels, animation.mNumChannels),
+            channels: utils::get_vec_from_raw(animation.mChannels, animation.mNumChannels)?,
             duration: animation.mDuration,
             morph_mesh_channels: utils::get_vec_from_raw(
                 animation.mMorphMeshChannels,
                 animation.mNumMorphMeshChannels,
-            ),
+            )?,
             mesh_channels: utils::get_vec_from_raw(
                 animation.mMeshChannels,
                 animation.mNumMeshChannels,
-            ),
+            )?,
             ticks_per_second: animation.mTicksPerSecond,
+        })
+    }
+}
+
+impl Animation {
+    /// Evaluates every [morph mesh channel](Animation::morph_mesh_channels) at
+    /// `time`, given in ticks.
+    ///
+    /// The weights can be handed to
+    /// [`Mesh::apply_morph_weights`](crate::mesh::Mesh::apply_morph_weights)
+    /// of the meshes whose name matches the channel.
+    pub fn sample_morph_weights(&self, time: f64) -> Vec<MorphWeights> {
+        self.morph_mesh_channels
+            .iter()
+            .map(|channel| MorphWeights {
+                name: channel.name.clone(),
+                weights: channel.sample(time),
+            })
+            .collect()
+    }
+
+    /// Cuts out the clip between `start` and `end`, given in ticks. The clip
+    /// starts at 0 and its duration is `end - start`.
+    pub fn trim(&self, start: f64, end: f64) -> Animation {
+        let start = start.max(0.0);
+        let end = end.max(start);
+
+        Animation {
+            name: self.name.clone(),
+            channels: self
+                .channels
+                .iter()
+                .map(|channel| channel.trim(start, end))
+                .collect(),
+            duration: end - start,
+            morph_mesh_channels: self
+                .morph_mesh_channels
+                .iter()
+                .map(|channel| channel.trim(start, end))
+                .collect(),
+            mesh_channels: self
+                .mesh_channels
+                .iter()
+                .map(|channel| channel.trim(start, end))
+                .collect(),
+            ticks_per_second: self.ticks_per_second,
+        }
+    }
+
+    /// Samples the node and morph channels `frames_per_second` times per
+    /// second, including a last sample at [`Animation::duration`]. Vertex
+    /// animation channels are stepped and kept as they are.
+    ///
+    /// Files that don't specify [`Animation::ticks_per_second`] are assumed to
+    /// use 25 ticks per second.
+    ///
+    /// Fails with [`RussimpError::InvalidArgument`] if `frames_per_second`
+    /// isn't positive and finite, or if the duration isn't finite.
+    pub fn resample(&self, frames_per_second: f64) -> Russult<Animation> {
+        if !frames_per_second.is_finite() || frames_per_second <= 0.0 {
+            return Err(RussimpError::InvalidArgument(format!(
+                "frames per second must be positive and finite, got {}",
+                frames_per_second
+            )));
+        }
+        if !self.duration.is_finite() {
+            return Err(RussimpError::InvalidArgument(format!(
+                "the duration of animation {} isn't finite",
+                self.name
+            )));
+        }
+
+        let ticks_per_second = if self.ticks_per_second > 0.0 {
+            self.ticks_per_second
+        } else {
+            DEFAULT_TICKS_PER_SECOND
+        };
+        let step = ticks_per_second / frames_per_second;
+        let duration = self.duration.max(0.0);
+        // don't put a second frame right before the last one
+        let mut times: Vec<f64> = (0u64..)
+            .map(|frame| frame as f64 * step)
+            .take_while(|&time| time < duration - step * 1e-3)
+            .collect();
+        times.push(duration);
+
+        Ok(Animation {
+            name: self.name.clone(),
+            channels: self
+                .channels
+                .iter()
+                .map(|channel| channel.resample(&times))
+                .collect(),
+            duration: self.duration,
+            morph_mesh_channels: self
+                .morph_mesh_channels
+                .iter()
+                .map(|channel| channel.resample(&times))
+                .collect(),
+            mesh_channels: self.mesh_channels.clone(),
+            ticks_per_second: self.ticks_per_second,
+        })
+    }
+
+    /// Removes redundant node channel keys, see [`NodeAnim::reduce_keys`].
+    pub fn reduce_keys(&self, tolerance: &KeyTolerance) -> Animation {
+        Animation {
+            name: self.name.clone(),
+            channels: self
+                .channels
+                .iter()
+                .map(|channel| channel.reduce_keys(tolerance))
+                .collect(),
+            duration: self.duration,
+            morph_mesh_channels: self.morph_mesh_channels.clone(),
+            mesh_channels: self.mesh_channels.clone(),
+            ticks_per_second: self.ticks_per_second,
         }
     }
 }
 
+const DEFAULT_TICKS_PER_SECOND: f64 = 25.0;
+
 #[cfg(test)]
 mod test {
-    use crate::utils;
+    use crate::{
+        animation::{
+            Animation, KeyTolerance, MeshAnim, MeshKey, MeshMorphAnim, MeshMorphKey, NodeAnim,
+            QuatKey, Quaternion, VectorKey,
+        },
+        utils, RussimpError, Vector3D,
+    };
+
+    fn walk_anim() -> NodeAnim {
+        let half_turn = std::f32::consts::FRAC_1_SQRT_2;
+
+        NodeAnim {
+            name: "Hips".to_string(),
+            position_keys: (0..=4)
+                .map(|frame| VectorKey {
+                    time: frame as f64 * 5.0,
+                    value: Vector3D::new(frame as f32, 0.0, 0.0),
+                })
+                .collect(),
+            rotation_keys: vec![
+                QuatKey {
+                    time: 0.0,
+                    value: Quaternion::identity(),
+                },
+                QuatKey {
+                    time: 20.0,
+                    value: Quaternion::new(half_turn, 0.0, half_turn, 0.0),
+                },
+            ],
+            scaling_keys: vec![
+                VectorKey {
+                    time: 0.0,
+                    value: Vector3D::new(1.0, 1.0, 1.0),
+                },
+                VectorKey {
+                    time: 20.0,
+                    value: Vector3D::new(1.0, 1.0, 1.0),
+                },
+            ],
+            post_state: 0,
+            pre_state: 0,
+        }
+    }
+
+    fn morph_anim() -> MeshMorphAnim {
+        MeshMorphAnim {
+            name: "Face".to_string(),
+            keys: vec![
+                MeshMorphKey {
+                    time: 0.0,
+                    values: vec![0],
+                    weights: vec![1.0],
+                },
+                MeshMorphKey {
+                    time: 10.0,
+                    values: vec![0, 2],
+                    weights: vec![0.0, 1.0],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn morph_weights_are_interpolated() {
+        let animation = Animation {
+            morph_mesh_channels: vec![morph_anim()],
+            ..Default::default()
+        };
+
+        let sampled = animation.sample_morph_weights(2.5);
+
+        assert_eq!(1, sampled.len());
+        assert_eq!("Face", sampled[0].name);
+        assert_eq!(vec![0.75, 0.0, 0.25], sampled[0].weights);
+    }
+
+    #[test]
+    fn morph_weights_are_clamped_to_keys() {
+        let anim = morph_anim();
+
+        assert_eq!(vec![1.0, 0.0, 0.0], anim.sample(-5.0));
+        assert_eq!(vec![0.0, 0.0, 1.0], anim.sample(10.0));
+        assert_eq!(vec![0.0, 0.0, 1.0], anim.sample(50.0));
+    }
+
+    #[test]
+    fn rotation_keys_are_slerped() {
+        let rotation = walk_anim().sample_rotation(10.0).unwrap();
+        let expected = std::f32::consts::FRAC_PI_8;
+
+        assert!((expected.cos() - rotation.w).abs() < 1e-5);
+        assert!((expected.sin() - rotation.y).abs() < 1e-5);
+    }
+
+    #[test]
+    fn trim_animation() {
+        let animation = Animation {
+            channels: vec![walk_anim()],
+            duration: 20.0,
+            morph_mesh_channels: vec![morph_anim()],
+            ..Default::default()
+        };
+
+        let trimmed = animation.trim(2.5, 12.5);
+
+        assert_eq!(10.0, trimmed.duration);
+
+        let channel = &trimmed.channels[0];
+        let times: Vec<f64> = channel.position_keys.iter().map(|key| key.time).collect();
+        assert_eq!(vec![0.0, 2.5, 7.5, 10.0], times);
+        assert_eq!(0.5, channel.position_keys[0].value.x);
+        assert_eq!(2.5, channel.position_keys[3].value.x);
+        assert_eq!(2, channel.rotation_keys.len());
+
+        let morph = &trimmed.morph_mesh_channels[0];
+        assert_eq!(vec![0.75, 0.0, 0.25], morph.sample(0.0));
+        assert_eq!(vec![0.0, 0.0, 1.0], morph.sample(10.0));
+    }
+
+    #[test]
+    fn resample_animation() {
+        let animation = Animation {
+            channels: vec![walk_anim()],
+            duration: 20.0,
+            ticks_per_second: 10.0,
+            ..Default::default()
+        };
+
+        let resampled = animation.resample(3.0).unwrap();
+
+        let channel = &resampled.channels[0];
+        assert_eq!(7, channel.position_keys.len());
+        assert_eq!(7, channel.rotation_keys.len());
+        assert_eq!(20.0, channel.position_keys[6].time);
+        assert_eq!(4.0, channel.position_keys[6].value.x);
+        assert_eq!(20.0, resampled.duration);
+    }
+
+    #[test]
+    fn resample_rejects_invalid_rates() {
+        let animation = Animation {
+            channels: vec![walk_anim()],
+            duration: 20.0,
+            ..Default::default()
+        };
+
+        for frames_per_second in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                animation.resample(frames_per_second),
+                Err(RussimpError::InvalidArgument(_))
+            ));
+        }
+
+        let endless = Animation {
+            duration: f64::INFINITY,
+            ..animation
+        };
+        assert!(matches!(
+            endless.resample(25.0),
+            Err(RussimpError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn reduce_animation_keys() {
+        let mut channel = walk_anim();
+        channel.position_keys[4].value.y = 1.0;
+
+        let animation = Animation {
+            channels: vec![channel],
+            duration: 20.0,
+            ..Default::default()
+        }
+        .resample(25.0)
+        .unwrap();
+
+        let reduced = animation.reduce_keys(&KeyTolerance::default());
+        let channel = &reduced.channels[0];
+
+        let times: Vec<f64> = channel.position_keys.iter().map(|key| key.time).collect();
+        assert_eq!(vec![0.0, 15.0, 20.0], times);
+        assert_eq!(2, channel.rotation_keys.len());
+        assert_eq!(1, channel.scaling_keys.len());
+    }
+
+    #[test]
+    fn mesh_anim_keys_are_stepped() {
+        let anim = MeshAnim {
+            name: "Cube".to_string(),
+            keys: vec![
+                MeshKey {
+                    time: 0.0,
+                    value: 3,
+                },
+                MeshKey {
+                    time: 5.0,
+                    value: 1,
+                },
+            ],
+        };
+
+        assert_eq!(Some(3), anim.sample(4.9));
+        assert_eq!(Some(1), anim.sample(5.0));
+        assert_eq!(
+            None,
+            MeshAnim {
+                name: String::new(),
+                keys: vec![]
+            }
+            .sample(1.0)
+        );
+    }
 
     #[cfg(not(target_os = "macos"))]
     #[test]
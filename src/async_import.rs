@@ -0,0 +1,338 @@
+//! The `async_import` module reads files with async IO and runs assimp on
+//! tokio's blocking thread pool. Converting the imported scene still runs on
+//! the awaiting task, see [`Scene::load_async`]. Requires the `async` feature.
+//!
+//! ```no_run
+//! # async fn load() -> russimp::Russult<()> {
+//! use russimp::{async_import::LoadOptions, scene::{PostProcess, Scene}};
+//!
+//! let options = LoadOptions {
+//!     flags: vec![PostProcess::Triangulate].into(),
+//!     prefetch: vec!["models/OBJ/cube.mtl".into()],
+//!     ..LoadOptions::default()
+//! };
+//! let scene = Scene::load_async("models/OBJ/cube.obj", options).await?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::{
+    config::ImportConfig,
+    fs::{AsyncFileSystem, FileOperations, FileSystem},
+    importer::Importer,
+    scene::{PostProcessFlags, Scene},
+    RussimpError, Russult,
+};
+use derivative::Derivative;
+use std::{
+    io::{self, ErrorKind, SeekFrom},
+    iter, panic,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// The options of [`Scene::load_async`].
+#[derive(Clone, Default, Derivative)]
+#[derivative(Debug)]
+pub struct LoadOptions {
+    pub flags: PostProcessFlags,
+    /// Applied to the [`PropertyStore`](crate::property::PropertyStore) of
+    /// the import.
+    pub configs: Vec<ImportConfig>,
+    /// Files the imported file references, e.g. the MTL of an OBJ, to read
+    /// with async IO before the import. The imported file is always
+    /// prefetched, see [`AsyncFileSystem`].
+    pub prefetch: Vec<PathBuf>,
+}
+
+/// Fails every file operation of assimp once the future of
+/// [`Scene::load_async`] was dropped, which aborts the parsing.
+struct CancellableFileSystem<T> {
+    inner: T,
+    cancelled: Arc<AtomicBool>,
+}
+
+fn check_cancelled(cancelled: &AtomicBool) -> io::Result<()> {
+    if cancelled.load(Ordering::Relaxed) {
+        return Err(io::Error::new(
+            ErrorKind::Interrupted,
+            "the import was cancelled",
+        ));
+    }
+
+    Ok(())
+}
+
+impl<T: FileSystem> FileSystem for CancellableFileSystem<T> {
+    fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn FileOperations>> {
+        check_cancelled(&self.cancelled)?;
+        Ok(Box::new(CancellableFile {
+            inner: self.inner.open(file_path, mode)?,
+            cancelled: self.cancelled.clone(),
+        }))
+    }
+}
+
+struct CancellableFile {
+    inner: Box<dyn FileOperations>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl FileOperations for CancellableFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        check_cancelled(&self.cancelled)?;
+        self.inner.read(buf)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn tell(&mut self) -> io::Result<usize> {
+        self.inner.tell()
+    }
+
+    fn size(&mut self) -> io::Result<usize> {
+        self.inner.size()
+    }
+
+    fn seek(&mut self, seek_from: SeekFrom) -> io::Result<()> {
+        check_cancelled(&self.cancelled)?;
+        self.inner.seek(seek_from)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    fn close(&mut self) {
+        self.inner.close()
+    }
+}
+
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Scene {
+    /// Imports a file on tokio's blocking thread pool, after reading it and
+    /// [`LoadOptions::prefetch`] with async IO.
+    ///
+    /// Assimp's result is converted to a [`Scene`] on the awaiting task, as
+    /// scenes can't be sent between threads. The conversion copies every
+    /// mesh, texture and animation and blocks the executor thread meanwhile,
+    /// for large scenes that can take a while.
+    ///
+    /// Dropping the future fails every following read, seek and open of
+    /// assimp, which aborts the parsing. Post processing that already started
+    /// runs to completion on the blocking thread pool, the scene is released
+    /// there afterwards.
+    pub async fn load_async<P: AsRef<Path>>(file_path: P, options: LoadOptions) -> Russult<Scene> {
+        let file_path = file_path.as_ref().to_path_buf();
+        let LoadOptions {
+            flags,
+            configs,
+            prefetch,
+        } = options;
+
+        let mut file_system = AsyncFileSystem::new();
+        for path in iter::once(&file_path).chain(&prefetch) {
+            file_system.prefetch(path).await?;
+        }
+
+        import(file_system, file_path, flags, configs).await
+    }
+}
+
+async fn import<T: FileSystem + Send + 'static>(
+    file_system: T,
+    file_path: PathBuf,
+    flags: PostProcessFlags,
+    configs: Vec<ImportConfig>,
+) -> Russult<Scene> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let _guard = CancelOnDrop(cancelled.clone());
+    let file_system = CancellableFileSystem {
+        inner: file_system,
+        cancelled,
+    };
+    let import = tokio::task::spawn_blocking(move || {
+        Importer::new()
+            .post_process(flags)
+            .properties(configs.into_iter().collect())
+            .file_system(&file_system)
+            .read_file_raw(&file_path)
+    });
+
+    match import.await {
+        Ok(raw_scene) => raw_scene?.convert(),
+        Err(error) if error.is_panic() => panic::resume_unwind(error.into_panic()),
+        Err(_) => Err(RussimpError::Cancelled),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        async_import::{self, LoadOptions},
+        fs::{FileOperations, FileSystem, StdFileSystem},
+        scene::{PostProcess, PostProcessFlags, Scene},
+        utils, RussimpError,
+    };
+    use std::{
+        future::{poll_fn, Future},
+        io::{self, SeekFrom},
+        pin::pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc, Arc, Mutex,
+        },
+        task::Poll,
+        time::Duration,
+    };
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    /// Pauses the first open until the test resumes it and counts the reads
+    /// that reach the opened files.
+    struct PausedFileSystem {
+        opened: mpsc::SyncSender<()>,
+        resume: Mutex<mpsc::Receiver<()>>,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl FileSystem for PausedFileSystem {
+        fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn FileOperations>> {
+            let _ = self.opened.try_send(());
+            let _ = self
+                .resume
+                .lock()
+                .unwrap()
+                .recv_timeout(Duration::from_secs(5));
+
+            Ok(Box::new(CountedFile {
+                inner: StdFileSystem::default().open(file_path, mode)?,
+                reads: self.reads.clone(),
+            }))
+        }
+    }
+
+    struct CountedFile {
+        inner: Box<dyn FileOperations>,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl FileOperations for CountedFile {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            self.inner.read(buf)
+        }
+
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.inner.write(buf)
+        }
+
+        fn tell(&mut self) -> io::Result<usize> {
+            self.inner.tell()
+        }
+
+        fn size(&mut self) -> io::Result<usize> {
+            self.inner.size()
+        }
+
+        fn seek(&mut self, seek_from: SeekFrom) -> io::Result<()> {
+            self.inner.seek(seek_from)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.inner.flush()
+        }
+
+        fn close(&mut self) {
+            self.inner.close()
+        }
+    }
+
+    #[test]
+    fn dropping_aborts_the_import() {
+        let (opened, on_open) = mpsc::sync_channel(1);
+        let (resume, on_resume) = mpsc::channel();
+        let reads = Arc::new(AtomicUsize::new(0));
+        let file_system = PausedFileSystem {
+            opened,
+            resume: Mutex::new(on_resume),
+            reads: reads.clone(),
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let mut import = pin!(async_import::import(
+                file_system,
+                utils::get_model("models/OBJ/cube.obj").into(),
+                PostProcessFlags::default(),
+                vec![],
+            ));
+
+            // the first poll starts the import, drop it once assimp opened the file
+            poll_fn(|cx| {
+                assert!(import.as_mut().poll(cx).is_pending());
+                Poll::Ready(())
+            })
+            .await;
+            on_open
+                .recv_timeout(Duration::from_secs(5))
+                .expect("assimp didn't open the file");
+        });
+        resume.send(()).unwrap();
+
+        // waits for the import on the blocking thread pool
+        drop(runtime);
+        assert_eq!(0, reads.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn missing_files_fail_before_the_import() {
+        let result = block_on(Scene::load_async(
+            "models/missing.obj",
+            LoadOptions::default(),
+        ));
+
+        assert!(matches!(result, Err(RussimpError::Io(_))));
+    }
+
+    #[test]
+    fn load_cube() {
+        let options = LoadOptions {
+            flags: vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ]
+            .into(),
+            prefetch: vec![utils::get_model("models/OBJ/cube.mtl").into()],
+            ..LoadOptions::default()
+        };
+
+        let scene = block_on(Scene::load_async(
+            utils::get_model("models/OBJ/cube.obj"),
+            options,
+        ))
+        .unwrap();
+
+        assert_eq!(2, scene.materials.len());
+    }
+}
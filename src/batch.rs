@@ -0,0 +1,170 @@
+//! The `batch` module imports many files at once on rayon's thread pool.
+//! Requires the `rayon` feature.
+//!
+//! [`Scene`] shares its nodes and textures through `Rc` and can't leave the
+//! thread that imported it, so the caller passes a function that turns every
+//! scene into a `Send` value right after its import:
+//!
+//! ```no_run
+//! use russimp::{batch::{self, BatchOptions}, scene::PostProcess};
+//!
+//! let options = BatchOptions {
+//!     flags: vec![PostProcess::Triangulate].into(),
+//!     ..BatchOptions::default()
+//! };
+//! let paths = ["models/OBJ/cube.obj", "models/FBX/y_bot_run.fbx"];
+//!
+//! for result in batch::import_all(&paths, &options, |scene| scene.meshes.len()) {
+//!     let path = result.path.display();
+//!     match result.result {
+//!         Ok((meshes, report)) => println!("{}: {} meshes, {:?}", path, meshes, report.importer),
+//!         Err(error) => println!("{}: {}", path, error),
+//!     }
+//! }
+//! ```
+use crate::{
+    config::ImportConfig,
+    importer::Importer,
+    report::{self, ImportReport},
+    scene::{PostProcessFlags, Scene},
+    Russult,
+};
+use derivative::Derivative;
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// The options every file of a batch is imported with.
+#[derive(Clone, Default, Derivative)]
+#[derivative(Debug)]
+pub struct BatchOptions {
+    pub flags: PostProcessFlags,
+    /// Applied to the [`PropertyStore`](crate::property::PropertyStore) of
+    /// every import.
+    pub configs: Vec<ImportConfig>,
+}
+
+/// The outcome of importing one file of a batch.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct BatchResult<T> {
+    pub path: PathBuf,
+    /// The converted scene and the report of its import, see
+    /// [`Scene::from_file_with_report`].
+    pub result: Russult<(T, ImportReport)>,
+}
+
+/// Imports `paths` in parallel, each file on its own thread with its own
+/// assimp importer, and converts every scene with `convert` on the thread
+/// that imported it.
+///
+/// The results are in the order of `paths`. A failed import doesn't stop the
+/// others.
+pub fn import_all<P, T, F>(paths: &[P], options: &BatchOptions, convert: F) -> Vec<BatchResult<T>>
+where
+    P: AsRef<Path> + Sync,
+    T: Send,
+    F: Fn(Scene) -> T + Sync,
+{
+    paths
+        .par_iter()
+        .map(|path| {
+            let path = path.as_ref();
+            BatchResult {
+                path: path.to_path_buf(),
+                result: import(path, options).map(|(scene, report)| (convert(scene), report)),
+            }
+        })
+        .collect()
+}
+
+fn import(path: &Path, options: &BatchOptions) -> Russult<(Scene, ImportReport)> {
+    let props = options.configs.iter().cloned().collect();
+    report::collect(props, |props| {
+        Importer::new()
+            .post_process(options.flags)
+            .borrowed_properties(props)
+            .read_file_raw(path)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        batch::{self, BatchOptions},
+        config::ImportConfig,
+        scene::PostProcess,
+        utils, RussimpError,
+    };
+
+    #[test]
+    fn results_keep_the_order_of_the_paths() {
+        let options = BatchOptions {
+            flags: vec![
+                PostProcess::GenerateNormals,
+                PostProcess::GenerateSmoothNormals,
+            ]
+            .into(),
+            ..BatchOptions::default()
+        };
+        let paths = ["a.obj", "b.obj", "c.obj"];
+
+        let results = batch::import_all(&paths, &options, |scene| scene.meshes.len());
+
+        for (path, result) in paths.iter().zip(&results) {
+            assert_eq!(path, &result.path.to_str().unwrap());
+            assert!(matches!(result.result, Err(RussimpError::PostProcess(_))));
+        }
+    }
+
+    #[test]
+    fn failures_keep_their_own_errors() {
+        let paths: Vec<_> = (0..32)
+            .map(|index| format!("models/missing_{}.obj", index))
+            .collect();
+
+        let results = batch::import_all(&paths, &BatchOptions::default(), |_| ());
+
+        for (path, result) in paths.iter().zip(results) {
+            match result.result {
+                Err(RussimpError::Import {
+                    message,
+                    path: Some(error_path),
+                }) => {
+                    assert_eq!(path, error_path.to_str().unwrap());
+                    assert!(message.contains(path.as_str()), "{}: {}", path, message);
+                }
+                result => panic!("{}: {:?}", path, result),
+            }
+        }
+    }
+
+    #[test]
+    fn import_models_in_parallel() {
+        let options = BatchOptions {
+            flags: vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ]
+            .into(),
+            configs: vec![ImportConfig::GlobalScale(2.0)],
+        };
+        let paths = [
+            utils::get_model("models/OBJ/cube.obj"),
+            utils::get_model("models/FBX/y_bot_run.fbx"),
+            utils::get_model("models/missing.blend"),
+        ];
+
+        let results = batch::import_all(&paths, &options, |scene| scene.meshes.len());
+
+        let (meshes, report) = results[0].result.as_ref().unwrap();
+        assert_eq!(1, *meshes);
+        assert!(report.importer.is_some());
+        assert!(results[1].result.is_ok());
+        assert!(matches!(
+            results[2].result,
+            Err(RussimpError::Import { .. })
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{sys::aiCamera, Vector3D};
+use crate::{node::Node, sys::aiCamera, Matrix4x4, Vector3D};
 use derivative::Derivative;
 
 #[derive(Derivative)]
@@ -10,6 +10,9 @@ pub struct Camera {
     pub clip_plane_near: f32,
     pub horizontal_fov: f32,
     pub look_at: Vector3D,
+    /// Half the horizontal width of the view volume in scene units, 0 for
+    /// perspective cameras.
+    pub orthographic_width: f32,
     pub position: Vector3D,
     pub up: Vector3D,
 }
@@ -23,14 +26,113 @@ impl From<&aiCamera> for Camera {
             clip_plane_near: camera.mClipPlaneNear,
             horizontal_fov: camera.mHorizontalFOV,
             look_at: (&camera.mLookAt).into(),
+            orthographic_width: camera.mOrthographicWidth,
             position: (&camera.mPosition).into(),
             up: (&camera.mUp).into(),
         }
     }
 }
 
+impl Camera {
+    pub fn is_orthographic(&self) -> bool {
+        self.orthographic_width > 0.0
+    }
+
+    /// Vertical field of view in radians, derived from
+    /// [`Camera::horizontal_fov`] and the aspect ratio, see
+    /// [`Camera::projection_matrix`].
+    pub fn vertical_fov(&self, aspect_override: Option<f32>) -> f32 {
+        let aspect = self.aspect_ratio(aspect_override);
+        2.0 * ((self.horizontal_fov / 2.0).tan() / aspect).atan()
+    }
+
+    /// Transformation from the space of the camera's node into view space,
+    /// where the camera sits at the origin and looks down the negative z
+    /// axis with y pointing up.
+    pub fn view_matrix(&self) -> Matrix4x4 {
+        let forward = self.look_at.normalize();
+        let right = forward.cross(&self.up).normalize();
+        let up = right.cross(&forward);
+
+        Matrix4x4 {
+            a1: right.x,
+            a2: right.y,
+            a3: right.z,
+            a4: -right.dot(&self.position),
+            b1: up.x,
+            b2: up.y,
+            b3: up.z,
+            b4: -up.dot(&self.position),
+            c1: -forward.x,
+            c2: -forward.y,
+            c3: -forward.z,
+            c4: forward.dot(&self.position),
+            ..Matrix4x4::identity()
+        }
+    }
+
+    /// Projection from view space into clip space, with depths between the
+    /// clip planes mapped to -1..1.
+    ///
+    /// `aspect_override` replaces [`Camera::aspect`], e.g. with the aspect
+    /// ratio of the viewport. Files that don't specify an aspect ratio store
+    /// 0, in which case 1 is used.
+    pub fn projection_matrix(&self, aspect_override: Option<f32>) -> Matrix4x4 {
+        let aspect = self.aspect_ratio(aspect_override);
+        let (near, far) = (self.clip_plane_near, self.clip_plane_far);
+
+        if self.is_orthographic() {
+            let half_width = self.orthographic_width;
+            let half_height = half_width / aspect;
+
+            Matrix4x4 {
+                a1: 1.0 / half_width,
+                b2: 1.0 / half_height,
+                c3: -2.0 / (far - near),
+                c4: -(far + near) / (far - near),
+                ..Matrix4x4::identity()
+            }
+        } else {
+            let focal_length = 1.0 / (self.vertical_fov(Some(aspect)) / 2.0).tan();
+
+            Matrix4x4 {
+                a1: focal_length / aspect,
+                b2: focal_length,
+                c3: (far + near) / (near - far),
+                c4: 2.0 * far * near / (near - far),
+                d3: -1.0,
+                d4: 0.0,
+                ..Matrix4x4::default()
+            }
+        }
+    }
+
+    /// Global transformation of the node with the same name as the camera,
+    /// searched below and including `root`.
+    pub fn world_transformation(&self, root: &Node) -> Option<Matrix4x4> {
+        root.global_transformation_of(&self.name)
+    }
+
+    /// Like [`Camera::view_matrix`], but transforming from world space, see
+    /// [`Camera::world_transformation`].
+    pub fn world_view_matrix(&self, root: &Node) -> Option<Matrix4x4> {
+        let world = self.world_transformation(root)?.inverse()?;
+        Some(self.view_matrix() * world)
+    }
+
+    fn aspect_ratio(&self, aspect_override: Option<f32>) -> f32 {
+        match aspect_override.unwrap_or(self.aspect) {
+            aspect if aspect > 0.0 => aspect,
+            _ => 1.0,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use crate::{camera::Camera, node::Node, Matrix4x4, Vector3D};
+    use std::rc::Rc;
+
     #[test]
     fn camera_available() {
         use crate::{
@@ -69,6 +171,164 @@ mod test {
         assert_eq!(0.0, scene.cameras[0].clip_plane_near);
         assert_eq!(1000.0, scene.cameras[0].clip_plane_far);
         assert_eq!("Camera01".to_string(), scene.cameras[0].name);
+        assert_eq!(0.0, scene.cameras[0].orthographic_width);
+    }
+
+    #[test]
+    fn camera_node() {
+        use crate::{
+            scene::{PostProcess, Scene},
+            utils,
+        };
+
+        let current_directory_buf = utils::get_model("models/3DS/CameraRollAnim.3ds");
+
+        let scene = Scene::from_file(
+            current_directory_buf.as_str(),
+            vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ],
+        )
+        .unwrap();
+
+        let camera = &scene.cameras[0];
+        let node = scene.camera_node(camera).unwrap();
+        assert_eq!("Camera01", node.name);
+
+        // assimp turns the z up scene of 3ds max to y up in the root node, the
+        // camera is placed by its first position key and barely rolled
+        let root = scene.root.as_ref().unwrap();
+        let world = camera.world_transformation(root).unwrap();
+        let position = world.transform_point(&Vector3D::default());
+        assert_close(-153.0771, position.x);
+        assert_close(22.777624, position.y);
+        assert_close(-3.272005, position.z);
+
+        let look_at = world.transform_vector(&camera.look_at);
+        let up = world.transform_vector(&camera.up);
+        for (expected, actual) in [
+            (0.0, look_at.x),
+            (1.0, look_at.y),
+            (0.0, look_at.z),
+            (0.0, up.x),
+            (0.0, up.y),
+            (-1.0, up.z),
+        ] {
+            // the roll of the first key is -0.053 degrees
+            assert!((expected - actual).abs() < 2e-3, "{:?} {:?}", look_at, up);
+        }
+        assert!(camera.world_view_matrix(root).is_some());
+    }
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 1e-5,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn camera() -> Camera {
+        Camera {
+            name: "Camera".to_string(),
+            aspect: 2.0,
+            clip_plane_far: 100.0,
+            clip_plane_near: 1.0,
+            horizontal_fov: std::f32::consts::FRAC_PI_2,
+            look_at: Vector3D::new(1.0, 0.0, 0.0),
+            orthographic_width: 0.0,
+            position: Vector3D::new(0.0, 0.0, 5.0),
+            up: Vector3D::new(0.0, 1.0, 0.0),
+        }
+    }
+
+    #[test]
+    fn camera_view_matrix() {
+        let view = camera().view_matrix();
+
+        // a point in front of the camera ends up on the negative z axis
+        let target = Matrix4x4 {
+            a4: 3.0,
+            c4: 5.0,
+            ..Matrix4x4::identity()
+        };
+        let target = view * target;
+        assert_close(0.0, target.a4);
+        assert_close(0.0, target.b4);
+        assert_close(-3.0, target.c4);
+    }
+
+    #[test]
+    fn camera_projection_matrix() {
+        let camera = camera();
+
+        assert_close(2.0 * 0.5f32.atan(), camera.vertical_fov(None));
+        assert_close(std::f32::consts::FRAC_PI_2, camera.vertical_fov(Some(1.0)));
+
+        let projection = camera.projection_matrix(None);
+        assert_close(1.0, projection.a1);
+        assert_close(2.0, projection.b2);
+        assert_close(-1.0, projection.d3);
+
+        let orthographic = Camera {
+            orthographic_width: 4.0,
+            ..camera
+        };
+        assert!(orthographic.is_orthographic());
+
+        let projection = orthographic.projection_matrix(Some(4.0));
+        assert_close(0.25, projection.a1);
+        assert_close(1.0, projection.b2);
+        assert_close(1.0, projection.d4);
+    }
+
+    #[test]
+    fn camera_world_transformation() {
+        let root = Rc::new(Node {
+            name: "Root".to_string(),
+            transformation: Matrix4x4 {
+                b4: 2.0,
+                ..Matrix4x4::identity()
+            },
+            ..Default::default()
+        });
+        let node = Rc::new(Node {
+            name: "Camera".to_string(),
+            transformation: Matrix4x4 {
+                a4: 1.0,
+                ..Matrix4x4::identity()
+            },
+            parent: Rc::downgrade(&root),
+            ..Default::default()
+        });
+        root.children.borrow_mut().push(node);
+
+        let camera = camera();
+        let world = camera.world_transformation(&root).unwrap();
+        assert_close(1.0, world.a4);
+        assert_close(2.0, world.b4);
+
+        let target = Matrix4x4 {
+            a4: 4.0,
+            b4: 2.0,
+            c4: 5.0,
+            ..Matrix4x4::identity()
+        };
+        let target = camera.world_view_matrix(&root).unwrap() * target;
+        assert_close(0.0, target.a4);
+        assert_close(0.0, target.b4);
+        assert_close(-3.0, target.c4);
+
+        assert!(Camera {
+            name: "Missing".to_string(),
+            ..camera
+        }
+        .world_transformation(&root)
+        .is_none());
     }
 
     #[test]
@@ -0,0 +1,376 @@
+//! The `config` module provides typed versions of assimp's `AI_CONFIG_*`
+//! import settings.
+//!
+//! Settings are collected in a [`PropertyStore`], which is handed to the
+//! import:
+//!
+//! ```no_run
+//! use russimp::{
+//!     config::{Components, ImportConfig},
+//!     importer::Importer,
+//!     property::PropertyStore,
+//!     scene::PostProcess,
+//! };
+//!
+//! let props: PropertyStore = [
+//!     ImportConfig::NormalsMaxSmoothingAngle(80.0),
+//!     ImportConfig::RemoveComponents(Components::COLORS | Components::CAMERAS),
+//!     ImportConfig::FbxPreservePivots(false),
+//! ]
+//! .into_iter()
+//! .collect();
+//!
+//! let scene = Importer::new()
+//!     .post_process(vec![
+//!         PostProcess::RemoveComponent,
+//!         PostProcess::GenerateSmoothNormals,
+//!     ])
+//!     .properties(props)
+//!     .read_file("models/FBX/y_bot_run.fbx");
+//! ```
+//!
+//! Only the commonly used keys are covered, format specific ones that are
+//! missing here can still be set by name through [`PropertyStore::set_integer`]
+//! and its siblings. Assimp's glTF importer doesn't read any settings of its
+//! own, the global ones like [`ImportConfig::GlobalScale`] apply to it.
+use crate::{
+    property::PropertyStore,
+    sys::{
+        aiComponent_aiComponent_ANIMATIONS, aiComponent_aiComponent_BONEWEIGHTS,
+        aiComponent_aiComponent_CAMERAS, aiComponent_aiComponent_COLORS,
+        aiComponent_aiComponent_LIGHTS, aiComponent_aiComponent_MATERIALS,
+        aiComponent_aiComponent_MESHES, aiComponent_aiComponent_NORMALS,
+        aiComponent_aiComponent_TANGENTS_AND_BITANGENTS, aiComponent_aiComponent_TEXCOORDS,
+        aiComponent_aiComponent_TEXTURES,
+    },
+};
+use bitflags::bitflags;
+use derivative::Derivative;
+
+bitflags! {
+    /// Parts of the scene removed by
+    /// [`PostProcess::RemoveComponent`](crate::scene::PostProcess::RemoveComponent),
+    /// see [`ImportConfig::RemoveComponents`].
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct Components: u32 {
+        const NORMALS = aiComponent_aiComponent_NORMALS as _;
+        const TANGENTS_AND_BITANGENTS = aiComponent_aiComponent_TANGENTS_AND_BITANGENTS as _;
+        /// All vertex color channels.
+        const COLORS = aiComponent_aiComponent_COLORS as _;
+        /// All texture coordinate channels.
+        const TEXCOORDS = aiComponent_aiComponent_TEXCOORDS as _;
+        /// Bone weights, the bones themselves are removed as well.
+        const BONE_WEIGHTS = aiComponent_aiComponent_BONEWEIGHTS as _;
+        const ANIMATIONS = aiComponent_aiComponent_ANIMATIONS as _;
+        /// Embedded textures.
+        const TEXTURES = aiComponent_aiComponent_TEXTURES as _;
+        const LIGHTS = aiComponent_aiComponent_LIGHTS as _;
+        const CAMERAS = aiComponent_aiComponent_CAMERAS as _;
+        /// Meshes with nothing else left to keep.
+        const MESHES = aiComponent_aiComponent_MESHES as _;
+        /// All materials, a default material is added afterwards.
+        const MATERIALS = aiComponent_aiComponent_MATERIALS as _;
+    }
+}
+
+/// A typed `AI_CONFIG_*` setting. The comments name the key every variant
+/// sets and assimp's default value.
+#[derive(Clone, Derivative, PartialEq)]
+#[derivative(Debug)]
+#[non_exhaustive]
+pub enum ImportConfig {
+    /// `GLOBAL_SCALE_FACTOR`, the factor applied by
+    /// [`PostProcess::GlobalScale`](crate::scene::PostProcess::GlobalScale).
+    /// Defaults to 1.
+    GlobalScale(f32),
+    /// `APP_SCALE_FACTOR`, an additional scale of the application, applied
+    /// together with [`ImportConfig::GlobalScale`]. Defaults to 1.
+    AppScale(f32),
+    /// `GLOB_MEASURE_TIME`, logs how long every step took.
+    MeasureTime(bool),
+    /// `FAVOUR_SPEED`, prefers faster algorithms over more accurate ones.
+    FavourSpeed(bool),
+    /// `IMPORT_NO_SKELETON_MESHES`, doesn't generate dummy meshes for files
+    /// that only contain a skeleton.
+    NoSkeletonMeshes(bool),
+    /// `AI_CONFIG_IMPORT_REMOVE_EMPTY_BONES`, removes bones without any
+    /// vertex weights. Defaults to true.
+    RemoveEmptyBones(bool),
+    /// `IMPORT_GLOBAL_KEYFRAME`, the keyframe to import from formats with
+    /// vertex animations like MD2 or MD3. Defaults to 0.
+    GlobalKeyframe(u32),
+
+    /// `PP_CT_MAX_SMOOTHING_ANGLE`, the maximum angle in degrees between
+    /// smoothed tangents. Defaults to 45.
+    TangentsMaxSmoothingAngle(f32),
+    /// `PP_CT_TEXTURE_CHANNEL_INDEX`, the texture coordinate channel the
+    /// tangents are calculated for. Defaults to 0.
+    TangentsTextureChannel(u32),
+    /// `PP_GSN_MAX_SMOOTHING_ANGLE`, the maximum angle in degrees between
+    /// smoothed normals. Defaults to 175.
+    NormalsMaxSmoothingAngle(f32),
+    /// `PP_SLM_VERTEX_LIMIT`, the vertex limit of
+    /// [`PostProcess::SplitLargeMeshes`](crate::scene::PostProcess::SplitLargeMeshes).
+    /// Defaults to 1000000.
+    SplitVertexLimit(u32),
+    /// `PP_SLM_TRIANGLE_LIMIT`, the triangle limit of
+    /// [`PostProcess::SplitLargeMeshes`](crate::scene::PostProcess::SplitLargeMeshes).
+    /// Defaults to 1000000.
+    SplitTriangleLimit(u32),
+    /// `PP_LBW_MAX_WEIGHTS`, the limit of
+    /// [`PostProcess::LimitBoneWeights`](crate::scene::PostProcess::LimitBoneWeights).
+    /// Defaults to 4.
+    MaxBoneWeights(u32),
+    /// `PP_SBBC_MAX_BONES`, the limit of
+    /// [`PostProcess::SplitByBoneCount`](crate::scene::PostProcess::SplitByBoneCount).
+    /// Defaults to 60.
+    MaxBonesPerMesh(u32),
+    /// `PP_RVC_FLAGS`, what
+    /// [`PostProcess::RemoveComponent`](crate::scene::PostProcess::RemoveComponent)
+    /// removes.
+    RemoveComponents(Components),
+    /// `PP_SBP_REMOVE`, the [`PrimitiveType`](crate::mesh::PrimitiveType)s
+    /// [`PostProcess::SortByPrimitiveType`](crate::scene::PostProcess::SortByPrimitiveType)
+    /// drops, e.g. `PrimitiveType::Point | PrimitiveType::Line`.
+    RemovePrimitiveTypes(u32),
+    /// `PP_FD_REMOVE`, removes degenerate primitives found by
+    /// [`PostProcess::FindDegenerates`](crate::scene::PostProcess::FindDegenerates)
+    /// instead of turning them into lines and points.
+    RemoveDegenerates(bool),
+    /// `PP_FD_CHECKAREA`, also treats triangles with a tiny area as
+    /// degenerate. Defaults to true.
+    CheckDegenerateArea(bool),
+    /// `PP_PTV_KEEP_HIERARCHY`, keeps the node hierarchy in
+    /// [`PostProcess::PreTransformVertices`](crate::scene::PostProcess::PreTransformVertices).
+    PreTransformKeepHierarchy(bool),
+    /// `PP_PTV_NORMALIZE`, scales the scene into the -1..1 range in
+    /// [`PostProcess::PreTransformVertices`](crate::scene::PostProcess::PreTransformVertices).
+    PreTransformNormalize(bool),
+    /// `PP_PTV_ROOT_TRANSFORMATION`, a column-major matrix
+    /// [`PostProcess::PreTransformVertices`](crate::scene::PostProcess::PreTransformVertices)
+    /// applies on top of the root node. Enables
+    /// `PP_PTV_ADD_ROOT_TRANSFORMATION` as well.
+    PreTransformRootTransformation([[f32; 4]; 4]),
+    /// `PP_ICL_PTCACHE_SIZE`, the vertex cache size
+    /// [`PostProcess::ImproveCacheLocality`](crate::scene::PostProcess::ImproveCacheLocality)
+    /// optimizes for. Defaults to 12.
+    CacheSize(u32),
+    /// `PP_DB_THRESHOLD`, the bone weight below which
+    /// [`PostProcess::Debone`](crate::scene::PostProcess::Debone) removes a
+    /// bone. Defaults to 1.
+    DeboneThreshold(f32),
+    /// `PP_DB_ALL_OR_NONE`, only removes bones if all of them qualify.
+    DeboneAllOrNone(bool),
+    /// `PP_FID_ANIM_ACCURACY`, the accuracy
+    /// [`PostProcess::FixOrRemoveInvalidData`](crate::scene::PostProcess::FixOrRemoveInvalidData)
+    /// compares animation keys with. Defaults to 0, i.e. exact comparisons.
+    InvalidDataAnimationAccuracy(f32),
+    /// `PP_FID_IGNORE_TEXTURECOORDS`, doesn't check texture coordinates in
+    /// [`PostProcess::FixOrRemoveInvalidData`](crate::scene::PostProcess::FixOrRemoveInvalidData).
+    InvalidDataIgnoreTextureCoords(bool),
+    /// `PP_RRM_EXCLUDE_LIST`, materials kept by
+    /// [`PostProcess::RemoveRedundantMaterials`](crate::scene::PostProcess::RemoveRedundantMaterials).
+    MaterialExcludeList(Vec<String>),
+    /// `PP_OG_EXCLUDE_LIST`, nodes kept by
+    /// [`PostProcess::OptimizeGraph`](crate::scene::PostProcess::OptimizeGraph).
+    GraphExcludeList(Vec<String>),
+
+    /// `IMPORT_FBX_READ_ALL_GEOMETRY_LAYERS`, reads every geometry layer
+    /// instead of only the first one. Defaults to true.
+    FbxReadAllGeometryLayers(bool),
+    /// `IMPORT_FBX_READ_ALL_MATERIALS`, also reads materials that no mesh
+    /// uses.
+    FbxReadAllMaterials(bool),
+    /// `IMPORT_FBX_READ_MATERIALS`. Defaults to true.
+    FbxReadMaterials(bool),
+    /// `IMPORT_FBX_READ_TEXTURES`, reads embedded textures. Defaults to
+    /// true.
+    FbxReadTextures(bool),
+    /// `IMPORT_FBX_READ_CAMERAS`. Defaults to true.
+    FbxReadCameras(bool),
+    /// `IMPORT_FBX_READ_LIGHTS`. Defaults to true.
+    FbxReadLights(bool),
+    /// `IMPORT_FBX_READ_ANIMATIONS`. Defaults to true.
+    FbxReadAnimations(bool),
+    /// `IMPORT_FBX_READ_WEIGHTS`. Defaults to true.
+    FbxReadWeights(bool),
+    /// `IMPORT_FBX_STRICT_MODE`, rejects files that don't follow the
+    /// specification exactly.
+    FbxStrictMode(bool),
+    /// `IMPORT_FBX_PRESERVE_PIVOTS`, keeps pivots as separate
+    /// `$AssimpFbx$` nodes. Defaults to true.
+    FbxPreservePivots(bool),
+    /// `IMPORT_FBX_OPTIMIZE_EMPTY_ANIMATION_CURVES`, drops animation curves
+    /// that don't change anything. Defaults to true.
+    FbxOptimizeEmptyAnimationCurves(bool),
+    /// `AI_CONFIG_IMPORT_FBX_EMBEDDED_TEXTURES_LEGACY_NAMING`, names embedded
+    /// textures like assimp versions before 4.0 did.
+    FbxEmbeddedTexturesLegacyNaming(bool),
+    /// `AI_CONFIG_FBX_CONVERT_TO_M`, converts centimeters to meters.
+    FbxConvertToMeters(bool),
+    /// `AI_CONFIG_FBX_USE_SKELETON_BONE_CONTAINER`, adds a container node
+    /// around the bones of a skeleton.
+    FbxUseSkeletonBoneContainer(bool),
+    /// `AI_CONFIG_IMPORT_FBX_IGNORE_UP_DIRECTION`, keeps the axes of the file
+    /// instead of converting them to Y up.
+    FbxIgnoreUpDirection(bool),
+}
+
+enum Value {
+    Integer(i32),
+    Float(f32),
+    String(String),
+    Matrix([[f32; 4]; 4]),
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Integer(value as _)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Value::Integer(value as _)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl ImportConfig {
+    /// The key of the setting and its value as assimp stores it.
+    fn entry(&self) -> (&'static [u8], Value) {
+        use ImportConfig::*;
+
+        match self {
+            GlobalScale(value) => (b"GLOBAL_SCALE_FACTOR", (*value).into()),
+            AppScale(value) => (b"APP_SCALE_FACTOR", (*value).into()),
+            MeasureTime(value) => (b"GLOB_MEASURE_TIME", (*value).into()),
+            FavourSpeed(value) => (b"FAVOUR_SPEED", (*value).into()),
+            NoSkeletonMeshes(value) => (b"IMPORT_NO_SKELETON_MESHES", (*value).into()),
+            RemoveEmptyBones(value) => (b"AI_CONFIG_IMPORT_REMOVE_EMPTY_BONES", (*value).into()),
+            GlobalKeyframe(value) => (b"IMPORT_GLOBAL_KEYFRAME", (*value).into()),
+            TangentsMaxSmoothingAngle(value) => (b"PP_CT_MAX_SMOOTHING_ANGLE", (*value).into()),
+            TangentsTextureChannel(value) => (b"PP_CT_TEXTURE_CHANNEL_INDEX", (*value).into()),
+            NormalsMaxSmoothingAngle(value) => (b"PP_GSN_MAX_SMOOTHING_ANGLE", (*value).into()),
+            SplitVertexLimit(value) => (b"PP_SLM_VERTEX_LIMIT", (*value).into()),
+            SplitTriangleLimit(value) => (b"PP_SLM_TRIANGLE_LIMIT", (*value).into()),
+            MaxBoneWeights(value) => (b"PP_LBW_MAX_WEIGHTS", (*value).into()),
+            MaxBonesPerMesh(value) => (b"PP_SBBC_MAX_BONES", (*value).into()),
+            RemoveComponents(value) => (b"PP_RVC_FLAGS", value.bits().into()),
+            RemovePrimitiveTypes(value) => (b"PP_SBP_REMOVE", (*value).into()),
+            RemoveDegenerates(value) => (b"PP_FD_REMOVE", (*value).into()),
+            CheckDegenerateArea(value) => (b"PP_FD_CHECKAREA", (*value).into()),
+            PreTransformKeepHierarchy(value) => (b"PP_PTV_KEEP_HIERARCHY", (*value).into()),
+            PreTransformNormalize(value) => (b"PP_PTV_NORMALIZE", (*value).into()),
+            PreTransformRootTransformation(value) => {
+                (b"PP_PTV_ROOT_TRANSFORMATION", Value::Matrix(*value))
+            }
+            CacheSize(value) => (b"PP_ICL_PTCACHE_SIZE", (*value).into()),
+            DeboneThreshold(value) => (b"PP_DB_THRESHOLD", (*value).into()),
+            DeboneAllOrNone(value) => (b"PP_DB_ALL_OR_NONE", (*value).into()),
+            InvalidDataAnimationAccuracy(value) => (b"PP_FID_ANIM_ACCURACY", (*value).into()),
+            InvalidDataIgnoreTextureCoords(value) => {
+                (b"PP_FID_IGNORE_TEXTURECOORDS", (*value).into())
+            }
+            MaterialExcludeList(names) => {
+                (b"PP_RRM_EXCLUDE_LIST", Value::String(exclude_list(names)))
+            }
+            GraphExcludeList(names) => (b"PP_OG_EXCLUDE_LIST", Value::String(exclude_list(names))),
+            FbxReadAllGeometryLayers(value) => {
+                (b"IMPORT_FBX_READ_ALL_GEOMETRY_LAYERS", (*value).into())
+            }
+            FbxReadAllMaterials(value) => (b"IMPORT_FBX_READ_ALL_MATERIALS", (*value).into()),
+            FbxReadMaterials(value) => (b"IMPORT_FBX_READ_MATERIALS", (*value).into()),
+            FbxReadTextures(value) => (b"IMPORT_FBX_READ_TEXTURES", (*value).into()),
+            FbxReadCameras(value) => (b"IMPORT_FBX_READ_CAMERAS", (*value).into()),
+            FbxReadLights(value) => (b"IMPORT_FBX_READ_LIGHTS", (*value).into()),
+            FbxReadAnimations(value) => (b"IMPORT_FBX_READ_ANIMATIONS", (*value).into()),
+            FbxReadWeights(value) => (b"IMPORT_FBX_READ_WEIGHTS", (*value).into()),
+            FbxStrictMode(value) => (b"IMPORT_FBX_STRICT_MODE", (*value).into()),
+            FbxPreservePivots(value) => (b"IMPORT_FBX_PRESERVE_PIVOTS", (*value).into()),
+            FbxOptimizeEmptyAnimationCurves(value) => (
+                b"IMPORT_FBX_OPTIMIZE_EMPTY_ANIMATION_CURVES",
+                (*value).into(),
+            ),
+            FbxEmbeddedTexturesLegacyNaming(value) => (
+                b"AI_CONFIG_IMPORT_FBX_EMBEDDED_TEXTURES_LEGACY_NAMING",
+                (*value).into(),
+            ),
+            FbxConvertToMeters(value) => (b"AI_CONFIG_FBX_CONVERT_TO_M", (*value).into()),
+            FbxUseSkeletonBoneContainer(value) => (
+                b"AI_CONFIG_FBX_USE_SKELETON_BONE_CONTAINER",
+                (*value).into(),
+            ),
+            FbxIgnoreUpDirection(value) => {
+                (b"AI_CONFIG_IMPORT_FBX_IGNORE_UP_DIRECTION", (*value).into())
+            }
+        }
+    }
+
+    /// Writes the setting into `props`.
+    pub(crate) fn apply(&self, props: &mut PropertyStore) {
+        if let ImportConfig::PreTransformRootTransformation(_) = self {
+            props.set_integer(b"PP_PTV_ADD_ROOT_TRANSFORMATION", 1);
+        }
+
+        let (name, value) = self.entry();
+        match value {
+            Value::Integer(value) => props.set_integer(name, value),
+            Value::Float(value) => props.set_float(name, value),
+            Value::String(value) => props.set_string(name, &value),
+            Value::Matrix(value) => props.set_matrix(name, value),
+        }
+    }
+}
+
+/// Assimp splits exclude lists at spaces, names are quoted so they may
+/// contain spaces themselves.
+fn exclude_list(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| format!("'{}'", name))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        config::{exclude_list, Components, ImportConfig},
+        importer::Importer,
+        property::PropertyStore,
+        scene::PostProcess,
+        utils,
+    };
+
+    #[test]
+    fn exclude_lists_are_quoted() {
+        let names = vec!["Body".to_string(), "Left Hand".to_string()];
+
+        assert_eq!("'Body' 'Left Hand'", exclude_list(&names));
+    }
+
+    #[test]
+    fn remove_components() {
+        let current_directory_buf = utils::get_model("models/OBJ/cube.obj");
+        let props: PropertyStore = [ImportConfig::RemoveComponents(
+            Components::NORMALS | Components::TEXCOORDS,
+        )]
+        .into_iter()
+        .collect();
+
+        let scene = Importer::new()
+            .post_process(vec![PostProcess::RemoveComponent])
+            .properties(props)
+            .read_file(current_directory_buf)
+            .unwrap();
+
+        assert!(scene.meshes[0].normals.is_empty());
+        assert!(scene.meshes[0].texture_coords.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,167 @@
+//! The `formats` module lists the file formats assimp can import.
+//!
+//! ```no_run
+//! use russimp::formats;
+//!
+//! for importer in formats::importers() {
+//!     println!("{}: {}", importer.name, importer.extensions.join(", "));
+//! }
+//!
+//! assert!(formats::is_extension_supported("fbx"));
+//! ```
+use crate::{
+    sys::{
+        aiGetImportFormatCount, aiGetImportFormatDescription, aiImporterDesc,
+        aiImporterFlags_aiImporterFlags_Experimental,
+        aiImporterFlags_aiImporterFlags_LimitedSupport,
+        aiImporterFlags_aiImporterFlags_SupportBinaryFlavour,
+        aiImporterFlags_aiImporterFlags_SupportCompressedFlavour,
+        aiImporterFlags_aiImporterFlags_SupportTextFlavour, aiIsExtensionSupported,
+    },
+    utils,
+};
+use bitflags::bitflags;
+use derivative::Derivative;
+use std::ffi::CString;
+
+bitflags! {
+    /// Decoded [`ImporterInfo::flags`].
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct ImporterFlags: u32 {
+        /// The format has a text flavour, e.g. ASCII FBX.
+        const TEXT = aiImporterFlags_aiImporterFlags_SupportTextFlavour as _;
+        /// The format has a binary flavour.
+        const BINARY = aiImporterFlags_aiImporterFlags_SupportBinaryFlavour as _;
+        /// The format has a compressed flavour.
+        const COMPRESSED = aiImporterFlags_aiImporterFlags_SupportCompressedFlavour as _;
+        /// Only parts of the format are supported, the importer may skip data.
+        const LIMITED_SUPPORT = aiImporterFlags_aiImporterFlags_LimitedSupport as _;
+        /// The importer is experimental and may crash or produce garbage.
+        const EXPERIMENTAL = aiImporterFlags_aiImporterFlags_Experimental as _;
+    }
+}
+
+/// Describes one of assimp's importers.
+#[derive(Clone, Derivative, PartialEq, Eq)]
+#[derivative(Debug)]
+pub struct ImporterInfo {
+    pub name: String,
+    pub author: String,
+    pub maintainer: String,
+    pub comments: String,
+    pub flags: ImporterFlags,
+    /// The oldest version of the format as `(major, minor)`, `None` if the
+    /// importer takes any version.
+    pub min_version: Option<(u32, u32)>,
+    /// The newest version of the format as `(major, minor)`, `None` if the
+    /// importer takes any version.
+    pub max_version: Option<(u32, u32)>,
+    /// The file extensions of the format without a leading dot, e.g. `obj`.
+    pub extensions: Vec<String>,
+}
+
+impl From<&aiImporterDesc> for ImporterInfo {
+    fn from(desc: &aiImporterDesc) -> Self {
+        // assimp uses 0.0 for formats without versions
+        let version = |major, minor| match (major, minor) {
+            (0, 0) => None,
+            version => Some(version),
+        };
+
+        Self {
+            name: utils::get_string(desc.mName),
+            author: utils::get_string(desc.mAuthor),
+            maintainer: utils::get_string(desc.mMaintainer),
+            comments: utils::get_string(desc.mComments),
+            flags: ImporterFlags::from_bits_retain(desc.mFlags),
+            min_version: version(desc.mMinMajor, desc.mMinMinor),
+            max_version: version(desc.mMaxMajor, desc.mMaxMinor),
+            extensions: utils::get_string(desc.mFileExtensions)
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Assimp expects extensions like `.obj`, callers may pass `obj` or `*.obj`
+/// as well.
+fn to_extension(extension: &str) -> Option<CString> {
+    let extension = extension.trim_start_matches(['*', '.']);
+    if extension.is_empty() {
+        return None;
+    }
+
+    CString::new(format!(".{}", extension)).ok()
+}
+
+/// All importers compiled into assimp.
+pub fn importers() -> Vec<ImporterInfo> {
+    let count = unsafe { aiGetImportFormatCount() };
+    (0..count)
+        .filter_map(|index| unsafe { aiGetImportFormatDescription(index).as_ref() })
+        .map(ImporterInfo::from)
+        .collect()
+}
+
+/// The importer for files with `extension`, e.g. `obj`, `.obj` or `*.obj`.
+/// The check is case insensitive.
+pub fn importer_for_extension(extension: &str) -> Option<ImporterInfo> {
+    let extension = to_extension(extension)?;
+    // assimp lists the extensions without dots
+    let extension = extension.to_str().ok()?.trim_start_matches('.');
+    importers().into_iter().find(|importer| {
+        importer
+            .extensions
+            .iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+    })
+}
+
+/// Whether assimp can import files with `extension`, e.g. `obj`, `.obj` or
+/// `*.obj`. The check is case insensitive.
+pub fn is_extension_supported(extension: &str) -> bool {
+    match to_extension(extension) {
+        Some(extension) => unsafe { aiIsExtensionSupported(extension.as_ptr()) != 0 },
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::formats::{self, to_extension};
+
+    #[test]
+    fn extensions_are_normalized() {
+        for extension in ["obj", ".obj", "*.obj"] {
+            assert_eq!(b".obj", to_extension(extension).unwrap().as_bytes());
+        }
+
+        assert!(to_extension("").is_none());
+        assert!(to_extension("*.").is_none());
+        assert!(to_extension("o\0bj").is_none());
+    }
+
+    #[test]
+    fn obj_is_supported() {
+        assert!(formats::is_extension_supported("obj"));
+        assert!(formats::is_extension_supported("*.OBJ"));
+        assert!(!formats::is_extension_supported("not-a-format"));
+
+        let importer = formats::importers()
+            .into_iter()
+            .find(|importer| {
+                importer
+                    .extensions
+                    .iter()
+                    .any(|extension| extension == "obj")
+            })
+            .unwrap();
+        assert_eq!(
+            Some(&importer),
+            formats::importer_for_extension(".obj").as_ref()
+        );
+        assert_eq!(Some(importer), formats::importer_for_extension("*.OBJ"));
+        assert!(formats::importer_for_extension("not-a-format").is_none());
+    }
+}
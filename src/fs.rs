@@ -2,91 +2,338 @@
 //!
 //! Implement the FileSystem trait for your custom resource loading, with its open() method returning
 //! objects satisfying the FileOperations trait.
+//!
+//! Assimp calls into these traits from C. Errors and panics are caught at that boundary and the
+//! first one is reported once the import returns: a panic is resumed, an error is returned as
+//! [`RussimpError::Io`](crate::RussimpError::Io) if the import failed.
+use derivative::Derivative;
 use russimp_sys::{aiFile, aiFileIO, aiOrigin, aiReturn};
-use std::convert::TryInto;
-use std::ffi::CStr;
-use std::io::SeekFrom;
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    ffi::CStr,
+    fs::File,
+    io::{self, Cursor, ErrorKind, Read, Seek, SeekFrom, Write},
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::Arc,
+};
 
 /// Implement FileSystem to use custom resource loading using `Scene::from_filesystem()`.
 ///
 /// Rusty version of the underlying aiFileIO type.
 pub trait FileSystem {
-    fn open(&self, file_path: &str, mode: &str) -> Option<Box<dyn FileOperations>>;
+    /// Opens `file_path`. `mode` is a C `fopen` mode like `rb`.
+    fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn FileOperations>>;
 }
 
 /// Implement this for a given resource to support custom resource loading.
 ///
 /// This trait class is the rusty version of the underlying aiFile type.
 pub trait FileOperations {
-    /// Should return the number of bytes read, or Err if read unsuccessful.
-    fn read(&mut self, buf: &mut [u8]) -> Result<usize, ()>;
-    /// Should return the number of bytes written, or Err if write unsuccessful.
-    fn write(&mut self, buf: &[u8]) -> Result<usize, ()>;
-    fn tell(&mut self) -> usize;
-    fn size(&mut self) -> usize;
-    fn seek(&mut self, seek_from: SeekFrom) -> Result<(), ()>;
-    fn flush(&mut self);
+    /// Should return the number of bytes read, 0 at the end of the file.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>;
+    /// Should return the number of bytes written.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize>;
+    fn tell(&mut self) -> io::Result<usize>;
+    fn size(&mut self) -> io::Result<usize>;
+    fn seek(&mut self, seek_from: SeekFrom) -> io::Result<()>;
+    fn flush(&mut self) -> io::Result<()>;
     fn close(&mut self);
 }
 
+#[cfg(feature = "zip")]
+mod archive;
+#[cfg(feature = "zip")]
+pub use archive::ZipFileSystem;
+#[cfg(feature = "async")]
+mod prefetch;
+#[cfg(feature = "async")]
+pub use prefetch::AsyncFileSystem;
+
+/// Opens files from disk, relative to a root directory.
+#[derive(Clone, Default, Derivative)]
+#[derivative(Debug)]
+pub struct StdFileSystem {
+    root: PathBuf,
+}
+
+impl StdFileSystem {
+    /// Paths are resolved relative to `root`. Absolute paths are opened as they are.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl FileSystem for StdFileSystem {
+    fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn FileOperations>> {
+        let path = self.root.join(file_path);
+        let file = if mode.contains('w') {
+            File::create(path)?
+        } else {
+            File::open(path)?
+        };
+
+        Ok(Box::new(ReadSeekFile::new(file)))
+    }
+}
+
+/// Serves files from memory, e.g. an OBJ with its MTL or a glTF with its buffers.
+///
+/// Paths are compared after resolving `.` and `..` and turning `\` into `/`, so
+/// `models/../cube.mtl` finds a file added as `cube.mtl`. Files can't be written.
+///
+/// ```no_run
+/// use russimp::{fs::MemoryFileSystem, scene::Scene};
+///
+/// let mut file_system = MemoryFileSystem::new();
+/// file_system.insert("cube.obj", std::fs::read("models/OBJ/cube.obj").unwrap());
+/// file_system.insert("cube.mtl", std::fs::read("models/OBJ/cube.mtl").unwrap());
+///
+/// let scene = Scene::from_file_system("cube.obj", vec![], &mut file_system).unwrap();
+/// ```
+#[derive(Clone, Default, Derivative)]
+#[derivative(Debug)]
+pub struct MemoryFileSystem {
+    #[derivative(Debug = "ignore")]
+    files: HashMap<String, Arc<[u8]>>,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the file at `path`, replacing an earlier file with the same path.
+    pub fn insert(&mut self, path: &str, data: impl Into<Vec<u8>>) {
+        self.files.insert(normalize_path(path), data.into().into());
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.files.contains_key(&normalize_path(path))
+    }
+}
+
+impl From<HashMap<String, Vec<u8>>> for MemoryFileSystem {
+    fn from(files: HashMap<String, Vec<u8>>) -> Self {
+        files.into_iter().collect()
+    }
+}
+
+impl<P: AsRef<str>, D: Into<Vec<u8>>> FromIterator<(P, D)> for MemoryFileSystem {
+    fn from_iter<I: IntoIterator<Item = (P, D)>>(iter: I) -> Self {
+        let mut file_system = Self::new();
+        for (path, data) in iter {
+            file_system.insert(path.as_ref(), data);
+        }
+
+        file_system
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn FileOperations>> {
+        if mode.contains('w') || mode.contains('a') || mode.contains('+') {
+            return Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                "the memory file system is read only",
+            ));
+        }
+
+        let data = self
+            .files
+            .get(&normalize_path(file_path))
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, file_path.to_string()))?;
+
+        Ok(Box::new(ReadSeekFile::new(Cursor::new(data.clone()))))
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    let mut components = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+
+    components.join("/")
+}
+
+/// Turns anything that can [`Read`] and [`Seek`] into [`FileOperations`], e.g. a
+/// [`File`] or a [`Cursor`]. Writing is supported if the file also implements
+/// [`Write`](std::io::Write), see [`ReadSeekFile::writable`].
+pub struct ReadSeekFile<R: Read + Seek> {
+    inner: R,
+    write: Option<WriteFn<R>>,
+    flush: Option<FlushFn<R>>,
+}
+
+type WriteFn<R> = fn(&mut R, &[u8]) -> io::Result<usize>;
+type FlushFn<R> = fn(&mut R) -> io::Result<()>;
+
+impl<R: Read + Seek> ReadSeekFile<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            write: None,
+            flush: None,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read + Seek + Write> ReadSeekFile<R> {
+    pub fn writable(inner: R) -> Self {
+        Self {
+            inner,
+            write: Some(R::write),
+            flush: Some(R::flush),
+        }
+    }
+}
+
+impl<R: Read + Seek> FileOperations for ReadSeekFile<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.write {
+            Some(write) => write(&mut self.inner, buf),
+            None => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "the file was opened for reading",
+            )),
+        }
+    }
+
+    fn tell(&mut self) -> io::Result<usize> {
+        Ok(self.inner.stream_position()? as usize)
+    }
+
+    fn size(&mut self) -> io::Result<usize> {
+        let position = self.inner.stream_position()?;
+        let size = self.inner.seek(SeekFrom::End(0))?;
+        self.inner.seek(SeekFrom::Start(position))?;
+
+        Ok(size as usize)
+    }
+
+    fn seek(&mut self, seek_from: SeekFrom) -> io::Result<()> {
+        self.inner.seek(seek_from).map(|_| ())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.flush {
+            Some(flush) => flush(&mut self.inner),
+            None => Ok(()),
+        }
+    }
+
+    fn close(&mut self) {}
+}
+
+/// What went wrong inside of a callback.
+pub(crate) enum CallbackError {
+    Io(io::Error),
+    Panic(Box<dyn Any + Send>),
+}
+
+/// Shared by the aiFileIO and every aiFile opened through it.
+struct IoState<'a> {
+    file_system: &'a dyn FileSystem,
+    /// The first error of the import, later ones are usually caused by it.
+    error: RefCell<Option<CallbackError>>,
+}
+
+impl<'a> IoState<'a> {
+    /// Runs `f` and records its error or panic, returning `fallback` in that case.
+    fn guard<R>(&self, fallback: R, f: impl FnOnce() -> io::Result<R>) -> R {
+        let error = match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(Ok(result)) => return result,
+            Ok(Err(error)) => CallbackError::Io(error),
+            Err(payload) => CallbackError::Panic(payload),
+        };
+
+        self.error.borrow_mut().get_or_insert(error);
+        fallback
+    }
+}
+
+/// Stored in the UserData of every aiFile.
+struct OpenFile<'a> {
+    file: Box<dyn FileOperations>,
+    state: &'a IoState<'a>,
+}
+
 /// This type allows us to generate C stubs for whatever trait object the user supplies.
-pub(crate) struct FileOperationsWrapper<T: FileSystem> {
+pub(crate) struct FileOperationsWrapper<'a> {
     ai_file: aiFileIO,
-    _phantom: std::marker::PhantomData<T>,
+    state: Box<IoState<'a>>,
 }
 
-impl<T: FileSystem> FileOperationsWrapper<T> {
+impl<'a> FileOperationsWrapper<'a> {
     /// Returns a wrapper that can create an aiFileIO to be used with the assimp C-API.
-    pub fn new(file_system: &T) -> FileOperationsWrapper<T> {
-        let trait_obj: &dyn FileSystem = file_system;
-        let managed_box = Box::new(trait_obj);
-        let user_data = Box::into_raw(managed_box);
-        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_os = "macos"))]
-        let user_data = user_data as *mut i8;
-        #[cfg(all(any(target_arch = "arm", target_arch = "aarch64"), not(target_os = "macos")))]
-        let user_data = user_data as *mut u8;
+    pub fn new(file_system: &'a dyn FileSystem) -> FileOperationsWrapper<'a> {
+        let state = Box::new(IoState {
+            file_system,
+            error: RefCell::new(None),
+        });
+        // The box keeps its address when the wrapper moves, it outlives every callback.
+        let user_data = &*state as *const IoState as *mut c_char;
         FileOperationsWrapper {
             ai_file: aiFileIO {
-                OpenProc: Some(FileOperationsWrapper::<T>::io_open),
-                CloseProc: Some(FileOperationsWrapper::<T>::io_close),
+                OpenProc: Some(Self::io_open),
+                CloseProc: Some(Self::io_close),
                 UserData: user_data,
             },
-            _phantom: Default::default(),
+            state,
         }
     }
     /// Get the aiFileIO to pass to the C-interface.
     pub fn ai_file(&mut self) -> &mut aiFileIO {
         &mut self.ai_file
     }
+    /// The first error or panic of a callback since the wrapper was created.
+    pub fn take_error(&mut self) -> Option<CallbackError> {
+        self.state.error.borrow_mut().take()
+    }
     /// Implementation for aiFileIO::OpenProc.
     unsafe extern "C" fn io_open(
         ai_file_io: *mut aiFileIO,
-        file_path: *const ::std::os::raw::c_char,
-        mode: *const ::std::os::raw::c_char,
+        file_path: *const c_char,
+        mode: *const c_char,
     ) -> *mut aiFile {
-        let file_system = Box::leak(Box::from_raw(
-            (*ai_file_io).UserData as *mut &dyn FileSystem,
-        ));
+        let state = &*((*ai_file_io).UserData as *const IoState);
 
-        let file_path = CStr::from_ptr(file_path)
-            .to_str()
-            .unwrap_or("Invalid UTF-8 Filename");
-        let mode = CStr::from_ptr(mode)
-            .to_str()
-            .unwrap_or("Invalid UTF-8 Mode");
-        let file = match file_system.open(file_path, mode) {
+        let file = state.guard(None, || {
+            let file_path = CStr::from_ptr(file_path)
+                .to_str()
+                .map_err(|error| io::Error::new(ErrorKind::InvalidInput, error))?;
+            let mode = CStr::from_ptr(mode)
+                .to_str()
+                .map_err(|error| io::Error::new(ErrorKind::InvalidInput, error))?;
+
+            state.file_system.open(file_path, mode).map(Some)
+        });
+        let file = match file {
             None => return std::ptr::null_mut(),
             Some(file) => file,
         };
 
-        // Take the returned file, and double box it here so that it can be converted to a single
-        // raw pointer that can be stuffed in the UserData.
-        let double_box = Box::new(file);
-        let managed_box = Box::into_raw(double_box); // Cleaned up in io_close.
-        #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_os = "macos"))]
-        let user_data = managed_box as *mut i8;
-        #[cfg(all(any(target_arch = "arm", target_arch = "aarch64"), not(target_os = "macos")))]
-        let user_data = managed_box as *mut u8;
+        // Box the file together with the state so that it can be converted to a single raw
+        // pointer that can be stuffed in the UserData.
+        let open_file = Box::into_raw(Box::new(OpenFile { file, state })); // Cleaned up in io_close.
         let ai_file = aiFile {
             ReadProc: Some(Self::io_read),
             WriteProc: Some(Self::io_write),
@@ -94,7 +341,7 @@ impl<T: FileSystem> FileOperationsWrapper<T> {
             FileSizeProc: Some(Self::io_size),
             SeekProc: Some(Self::io_seek),
             FlushProc: Some(Self::io_flush),
-            UserData: user_data,
+            UserData: open_file as *mut c_char,
         };
         // Lifetime of ai_file is managed by backend assimp library, cleaned up in io_close().
         Box::into_raw(Box::new(ai_file))
@@ -102,214 +349,177 @@ impl<T: FileSystem> FileOperationsWrapper<T> {
 
     /// Implementation for aiFileIO::CloseProc.
     unsafe extern "C" fn io_close(_ai_file_io: *mut aiFileIO, ai_file: *mut aiFile) {
+        if ai_file.is_null() {
+            return;
+        }
+
         // Given that this is close, we are careful to not leak, but instead drop the file when we
         // exit this scope.
         let ai_file = Box::from_raw(ai_file);
-        let mut file: Box<Box<dyn FileOperations>> =
-            Box::from_raw((*ai_file).UserData as *mut Box<dyn FileOperations>);
-        file.close();
+        let mut open_file = Box::from_raw(ai_file.UserData as *mut OpenFile);
+        let state = open_file.state;
+        state.guard((), || {
+            open_file.file.close();
+            drop(open_file);
+            Ok(())
+        });
     }
     /// Turn an aiFile pointer into a the "self" object.
     ///
     /// Safety: Only safe to call once from within each of the io_* callbacks. This assumes that
     /// the loading library has ownership of the aiFile object that was returned by the FileSystem.
-    /// It is expected to only be called serially on a single thread for the lifetype 'a, which
+    /// It is expected to only be called serially on a single thread for the lifetype 'b, which
     /// *should* keep access scoped to within the io_* callback.
-    unsafe fn get_file<'a>(ai_file: *mut aiFile) -> &'a mut Box<dyn FileOperations> {
-        // We return a "leaked" pointer here, using the saved off double box pointer that we
-        // stuffed in the UserData. We "leak" as we don't acutally want to return ownership, only
-        // the mutable reference. The box is manually cleaned up as part of io_close.
-        Box::leak(Box::from_raw(
-            (*ai_file).UserData as *mut Box<dyn FileOperations>,
-        ))
+    unsafe fn get_file<'b>(ai_file: *mut aiFile) -> &'b mut OpenFile<'b> {
+        // The box is manually cleaned up as part of io_close.
+        &mut *((*ai_file).UserData as *mut OpenFile)
     }
     // Implementation for aiFile::ReadProc.
     unsafe extern "C" fn io_read(
         ai_file: *mut aiFile,
-        buffer: *mut std::os::raw::c_char,
+        buffer: *mut c_char,
         size: usize,
         count: usize,
     ) -> usize {
-        let file = Self::get_file(ai_file);
-        let mut buffer =
-            std::slice::from_raw_parts_mut(buffer as *mut u8, (size * count).try_into().unwrap());
-        if size == 0 {
-            panic!("Size 0 is invalid");
-        }
-        if count == 0 {
-            panic!("Count 0 is invalid");
-        }
-        if size > std::usize::MAX {
-            panic!("huge read size not supported");
-        }
-        let size = size as usize;
-        if size == 1 {
-            // This looks like a memcpy.
-            if count > std::usize::MAX {
-                panic!("huge read not supported");
+        let OpenFile { file, state } = Self::get_file(ai_file);
+        state.guard(0, || {
+            let len = element_bytes(size, count)?;
+            if len == 0 {
+                return Ok(0);
             }
-            let count = count as usize;
 
-            let (buffer, _) = buffer.split_at_mut(count);
-            match file.read(buffer) {
-                Ok(size) => size,
-                Err(_) => std::usize::MAX,
-            }
-        } else {
-            // We have to copy in strides. Implement this by looping for each object and tally the
-            // count of full objects read.
-            let mut total: usize = 0;
-            for _ in 0..count {
-                let split = buffer.split_at_mut(size as usize);
-                buffer = split.1;
-                let bytes_read = match file.read(split.0) {
-                    Err(_) => break,
-                    Ok(bytes_read) => bytes_read,
-                };
-                if bytes_read != size {
-                    break;
+            let buffer = std::slice::from_raw_parts_mut(buffer as *mut u8, len);
+            let mut total = 0;
+            // Assimp takes a short read for the end of the file, so fill the buffer as far as
+            // possible and report the number of complete objects.
+            while total < len {
+                match file.read(&mut buffer[total..]) {
+                    Ok(0) => break,
+                    Ok(read) => total += read.min(len - total),
+                    Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                    Err(error) => return Err(error),
                 }
-                total = total + 1;
             }
-            total
-        }
+
+            Ok(total / size)
+        })
     }
     // Implementation for aiFile::WriteProc.
     unsafe extern "C" fn io_write(
         ai_file: *mut aiFile,
-        buffer: *const std::os::raw::c_char,
+        buffer: *const c_char,
         size: usize,
         count: usize,
     ) -> usize {
-        let file = Self::get_file(ai_file);
-        let mut buffer =
-            std::slice::from_raw_parts(buffer as *mut u8, (size * count).try_into().unwrap());
-        if size == 0 {
-            panic!("Write of size 0");
-        }
-        if count == 0 {
-            panic!("Write of count 0");
-        }
-        if size > std::usize::MAX {
-            panic!("huge write size not supported");
-        }
-        let size = size as usize;
-        if size == 1 {
-            if count > std::usize::MAX {
-                panic!("huge write not supported");
+        let OpenFile { file, state } = Self::get_file(ai_file);
+        state.guard(0, || {
+            let len = element_bytes(size, count)?;
+            if len == 0 {
+                return Ok(0);
             }
-            let count = count as usize;
 
-            let (buffer, _) = buffer.split_at(count);
-            match file.write(buffer) {
-                Ok(size) => size,
-                Err(_) => std::usize::MAX,
-            }
-        } else {
-            // Write in strides. Implement this by looping for each object and tally the
-            // count of full objects written.
-            let mut total: usize = 0;
-            for _ in 0..count {
-                let split = buffer.split_at(size as usize);
-                buffer = split.1;
-                let bytes_written = match file.write(split.0) {
-                    Err(_) => break,
-                    Ok(bytes_written) => bytes_written,
-                };
-                if bytes_written != size {
-                    break;
+            let buffer = std::slice::from_raw_parts(buffer as *const u8, len);
+            let mut total = 0;
+            while total < len {
+                match file.write(&buffer[total..]) {
+                    Ok(0) => break,
+                    Ok(written) => total += written.min(len - total),
+                    Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                    Err(error) => return Err(error),
                 }
-                total = total + 1;
             }
-            total
-        }
+
+            Ok(total / size)
+        })
     }
     // Implementation for aiFile::TellProc.
     unsafe extern "C" fn io_tell(ai_file: *mut aiFile) -> usize {
-        let file = Self::get_file(ai_file);
-        file.tell()
+        let OpenFile { file, state } = Self::get_file(ai_file);
+        state.guard(0, || file.tell())
     }
     // Implementation for aiFile::FileSizeProc.
     unsafe extern "C" fn io_size(ai_file: *mut aiFile) -> usize {
-        let file = Self::get_file(ai_file);
-        file.size()
+        let OpenFile { file, state } = Self::get_file(ai_file);
+        state.guard(0, || file.size())
     }
     // Implementation for aiFile::SeekProc.
     unsafe extern "C" fn io_seek(ai_file: *mut aiFile, pos: usize, origin: aiOrigin) -> aiReturn {
-        let file = Self::get_file(ai_file);
-        let seek_from = match origin {
-            russimp_sys::aiOrigin_aiOrigin_SET => SeekFrom::Start(pos as u64),
-            russimp_sys::aiOrigin_aiOrigin_CUR => SeekFrom::Current(pos as i64),
-            russimp_sys::aiOrigin_aiOrigin_END => SeekFrom::End(pos as i64),
-            _ => panic!("Assimp passed invalid origin"),
-        };
-        match file.seek(seek_from) {
-            Ok(()) => 0,
-            Err(()) => russimp_sys::aiReturn_aiReturn_FAILURE,
-        }
+        let OpenFile { file, state } = Self::get_file(ai_file);
+        state.guard(russimp_sys::aiReturn_aiReturn_FAILURE, || {
+            let seek_from = match origin {
+                russimp_sys::aiOrigin_aiOrigin_SET => SeekFrom::Start(pos as u64),
+                russimp_sys::aiOrigin_aiOrigin_CUR => SeekFrom::Current(pos as i64),
+                russimp_sys::aiOrigin_aiOrigin_END => SeekFrom::End(pos as i64),
+                _ => {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("assimp passed the invalid seek origin {}", origin),
+                    ))
+                }
+            };
+
+            file.seek(seek_from)?;
+            Ok(russimp_sys::aiReturn_aiReturn_SUCCESS)
+        })
     }
     // Implementation for aiFile::FlushProc.
     unsafe extern "C" fn io_flush(ai_file: *mut aiFile) {
-        let file = Self::get_file(ai_file);
-        file.flush();
+        let OpenFile { file, state } = Self::get_file(ai_file);
+        state.guard((), || file.flush())
     }
 }
 
-impl<T: FileSystem> Drop for FileOperationsWrapper<T> {
-    fn drop(&mut self) {
-        // Re-construct and drop the box that was used for the C-API.
-        let _managed_box: Box<&dyn FileSystem> =
-            unsafe { Box::from_raw(self.ai_file.UserData as *mut &dyn FileSystem) };
-    }
+/// Length in bytes of `count` objects of `size` bytes.
+fn element_bytes(size: usize, count: usize) -> io::Result<usize> {
+    size.checked_mul(count).ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} objects of {} bytes don't fit into memory", count, size),
+        )
+    })
 }
 
 #[cfg(test)]
 mod test {
+    use crate::fs::{
+        CallbackError, FileOperations, FileOperationsWrapper, FileSystem, MemoryFileSystem,
+        ReadSeekFile, StdFileSystem,
+    };
     use crate::scene::PostProcess;
     use crate::scene::Scene;
     use crate::utils;
+    use std::ffi::CString;
     use std::fs::File;
-    use std::io::{prelude::*, SeekFrom};
+    use std::io::{self, prelude::*, Cursor, ErrorKind, SeekFrom};
 
     struct MyFileOperations {
         file: File,
     }
 
     impl super::FileOperations for MyFileOperations {
-        fn read(&mut self, buf: &mut [u8]) -> Result<usize, ()> {
-            self.file.read(buf).or_else(|_| Err(()))
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.file.read(buf)
         }
 
-        fn write(&mut self, _buf: &[u8]) -> Result<usize, ()> {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
             unimplemented!("write support");
         }
 
-        fn tell(&mut self) -> usize {
-            self.file
-                .seek(SeekFrom::Current(0))
-                .unwrap_or(0)
-                .try_into()
-                .unwrap_or(0)
+        fn tell(&mut self) -> io::Result<usize> {
+            Ok(self.file.stream_position()? as usize)
         }
 
-        fn size(&mut self) -> usize {
-            self.file
-                .metadata()
-                .expect("Missing metadata")
-                .len()
-                .try_into()
-                .unwrap_or(0)
+        fn size(&mut self) -> io::Result<usize> {
+            Ok(self.file.metadata()?.len() as usize)
         }
 
-        fn seek(&mut self, seek_from: SeekFrom) -> Result<(), ()> {
-            match self.file.seek(seek_from) {
-                Ok(_) => Ok(()),
-                Err(_) => Err(()),
-            }
+        fn seek(&mut self, seek_from: SeekFrom) -> io::Result<()> {
+            self.file.seek(seek_from).map(|_| ())
         }
 
-        fn flush(&mut self) {
+        fn flush(&mut self) -> io::Result<()> {
             // write suppot not implemented.
+            Ok(())
         }
 
         fn close(&mut self) {
@@ -320,11 +530,11 @@ mod test {
     struct MyFS {}
 
     impl super::FileSystem for MyFS {
-        fn open(&self, file_path: &str, mode: &str) -> Option<Box<dyn super::FileOperations>> {
+        fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn super::FileOperations>> {
             // We only support reading for this test.
             assert_eq!(mode, "rb");
-            let file = File::open(file_path).expect("Couldn't open {file_path}");
-            Some(Box::new(MyFileOperations { file }))
+            let file = File::open(file_path)?;
+            Ok(Box::new(MyFileOperations { file }))
         }
     }
 
@@ -349,4 +559,212 @@ mod test {
         assert_eq!(scene.meshes[0].texture_coords.len(), 8);
         assert_eq!(scene.materials.len(), 2);
     }
+
+    /// Fails every read and panics when asked for its size.
+    struct BrokenFile;
+
+    impl FileOperations for BrokenFile {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn tell(&mut self) -> io::Result<usize> {
+            Ok(0)
+        }
+
+        fn size(&mut self) -> io::Result<usize> {
+            panic!("size panicked")
+        }
+
+        fn seek(&mut self, _seek_from: SeekFrom) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn close(&mut self) {}
+    }
+
+    struct BrokenFS;
+
+    impl FileSystem for BrokenFS {
+        fn open(&self, file_path: &str, _mode: &str) -> io::Result<Box<dyn FileOperations>> {
+            match file_path {
+                "missing" => Err(ErrorKind::NotFound.into()),
+                "panic" => panic!("open panicked"),
+                _ => Ok(Box::new(BrokenFile)),
+            }
+        }
+    }
+
+    #[test]
+    fn callback_errors_are_captured() {
+        let mut wrapper = FileOperationsWrapper::new(&BrokenFS);
+        let ai_file_io: *mut _ = wrapper.ai_file();
+        let mode = CString::new("rb").unwrap();
+
+        unsafe {
+            let open = (*ai_file_io).OpenProc.unwrap();
+            let close = (*ai_file_io).CloseProc.unwrap();
+
+            let path = CString::new("missing").unwrap();
+            assert!(open(ai_file_io, path.as_ptr(), mode.as_ptr()).is_null());
+            assert!(matches!(
+                wrapper.take_error(),
+                Some(CallbackError::Io(error)) if error.kind() == ErrorKind::NotFound
+            ));
+
+            let path = CString::new("panic").unwrap();
+            assert!(open(ai_file_io, path.as_ptr(), mode.as_ptr()).is_null());
+            assert!(matches!(
+                wrapper.take_error(),
+                Some(CallbackError::Panic(_))
+            ));
+
+            let path = CString::new("broken").unwrap();
+            let ai_file = open(ai_file_io, path.as_ptr(), mode.as_ptr());
+            assert!(!ai_file.is_null());
+
+            let mut buf = [0; 4];
+            let read = (*ai_file).ReadProc.unwrap();
+            assert_eq!(0, read(ai_file, buf.as_mut_ptr(), 1, 4));
+            // an empty read doesn't reach the file
+            assert_eq!(0, read(ai_file, buf.as_mut_ptr(), 0, 4));
+            assert_eq!(0, ((*ai_file).FileSizeProc.unwrap())(ai_file));
+            assert_eq!(
+                russimp_sys::aiReturn_aiReturn_FAILURE,
+                ((*ai_file).SeekProc.unwrap())(ai_file, 0, 42)
+            );
+            close(ai_file_io, ai_file);
+
+            // only the first error is kept
+            assert!(matches!(
+                wrapper.take_error(),
+                Some(CallbackError::Io(error)) if error.to_string() == "disk on fire"
+            ));
+            assert!(wrapper.take_error().is_none());
+        }
+    }
+
+    #[test]
+    fn non_utf8_paths_are_rejected() {
+        let mut wrapper = FileOperationsWrapper::new(&BrokenFS);
+        let ai_file_io: *mut _ = wrapper.ai_file();
+        let path = CString::new(&b"models/\xff.obj"[..]).unwrap();
+        let mode = CString::new("rb").unwrap();
+
+        unsafe {
+            let open = (*ai_file_io).OpenProc.unwrap();
+            assert!(open(ai_file_io, path.as_ptr(), mode.as_ptr()).is_null());
+        }
+        assert!(matches!(
+            wrapper.take_error(),
+            Some(CallbackError::Io(error)) if error.kind() == ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn import_reports_file_system_errors() {
+        let result = Scene::from_file_system("missing", vec![], &mut BrokenFS);
+
+        assert!(
+            matches!(result, Err(crate::RussimpError::Io(error)) if error.kind() == ErrorKind::NotFound)
+        );
+    }
+
+    fn read_to_end(file: &mut dyn FileOperations) -> Vec<u8> {
+        let mut data = vec![];
+        let mut buf = [0; 16];
+        loop {
+            match file.read(&mut buf).unwrap() {
+                0 => return data,
+                read => data.extend_from_slice(&buf[..read]),
+            }
+        }
+    }
+
+    #[test]
+    fn std_file_system_is_rooted() {
+        let file_system = StdFileSystem::new(utils::get_model("models/OBJ"));
+
+        let mut file = file_system.open("cube.mtl", "rb").unwrap();
+        let data = read_to_end(file.as_mut());
+
+        assert_eq!(
+            std::fs::read(utils::get_model("models/OBJ/cube.mtl")).unwrap(),
+            data
+        );
+        assert_eq!(data.len(), file.size().unwrap());
+        assert_eq!(
+            ErrorKind::NotFound,
+            file_system.open("missing.mtl", "rb").err().unwrap().kind()
+        );
+    }
+
+    #[test]
+    fn memory_file_system_normalizes_paths() {
+        let file_system: MemoryFileSystem = [("textures\\wood.txt", "wood")].into_iter().collect();
+
+        assert!(file_system.contains("./textures/wood.txt"));
+        let mut file = file_system
+            .open("models/../textures/wood.txt", "rb")
+            .unwrap();
+        assert_eq!(b"wood".to_vec(), read_to_end(file.as_mut()));
+
+        assert_eq!(
+            ErrorKind::NotFound,
+            file_system.open("wood.txt", "rb").err().unwrap().kind()
+        );
+        assert_eq!(
+            ErrorKind::PermissionDenied,
+            file_system
+                .open("textures/wood.txt", "wb")
+                .err()
+                .unwrap()
+                .kind()
+        );
+    }
+
+    #[test]
+    fn read_seek_file() {
+        let mut file = ReadSeekFile::new(Cursor::new(b"0123456789".to_vec()));
+
+        file.seek(SeekFrom::Start(4)).unwrap();
+        assert_eq!(10, file.size().unwrap());
+        assert_eq!(4, file.tell().unwrap());
+        assert!(file.write(b"x").is_err());
+
+        let mut file = ReadSeekFile::writable(Cursor::new(vec![]));
+        assert_eq!(3, file.write(b"abc").unwrap());
+        assert_eq!(b"abc".to_vec(), file.into_inner().into_inner());
+    }
+
+    #[test]
+    fn import_from_memory_file_system() {
+        let mut file_system = MemoryFileSystem::new();
+        for name in ["cube.obj", "cube.mtl"] {
+            let data = std::fs::read(utils::get_model(&format!("models/OBJ/{}", name))).unwrap();
+            file_system.insert(name, data);
+        }
+
+        let scene = Scene::from_file_system(
+            "cube.obj",
+            vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ],
+            &mut file_system,
+        )
+        .unwrap();
+
+        assert_eq!(scene.materials.len(), 2);
+    }
 }
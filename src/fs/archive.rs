@@ -0,0 +1,195 @@
+use super::{normalize_path, FileOperations, FileSystem, ReadSeekFile};
+use derivative::Derivative;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::File,
+    io::{self, Cursor, ErrorKind, Read, Seek},
+    path::Path,
+    sync::Arc,
+};
+use zip::{result::ZipError, ZipArchive};
+
+/// Opens files inside of a zip archive, e.g. a glTF with its buffers and
+/// textures.
+///
+/// Paths are resolved like in [`MemoryFileSystem`](super::MemoryFileSystem),
+/// relative to the root of the archive. Entries are decompressed the first
+/// time they are opened and kept in memory afterwards.
+///
+/// ```no_run
+/// use russimp::{fs::ZipFileSystem, scene::Scene};
+///
+/// let mut file_system = ZipFileSystem::open("assets.zip").unwrap();
+/// let scene = Scene::from_file_system("BoxTextured-GLTF/BoxTextured.gltf", vec![], &mut file_system);
+/// ```
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct ZipFileSystem<R: Read + Seek = File> {
+    #[derivative(Debug = "ignore")]
+    archive: RefCell<ZipArchive<R>>,
+    /// Index of every file in the archive, by normalized path.
+    entries: HashMap<String, usize>,
+    #[derivative(Debug = "ignore")]
+    extracted: RefCell<HashMap<usize, Arc<[u8]>>>,
+}
+
+impl ZipFileSystem<File> {
+    /// Opens the zip archive at `path`.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> ZipFileSystem<R> {
+    /// Reads the directory of the zip archive in `reader`.
+    pub fn new(reader: R) -> io::Result<Self> {
+        let mut archive = ZipArchive::new(reader).map_err(zip_error)?;
+        let mut entries = HashMap::new();
+        for index in 0..archive.len() {
+            let entry = archive.by_index_raw(index).map_err(zip_error)?;
+            if entry.is_file() {
+                entries.insert(normalize_path(entry.name()), index);
+            }
+        }
+
+        Ok(Self {
+            archive: RefCell::new(archive),
+            entries,
+            extracted: RefCell::new(HashMap::new()),
+        })
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(&normalize_path(path))
+    }
+
+    fn extract(&self, index: usize) -> io::Result<Arc<[u8]>> {
+        if let Some(data) = self.extracted.borrow().get(&index) {
+            return Ok(data.clone());
+        }
+
+        let mut archive = self.archive.borrow_mut();
+        let mut entry = archive.by_index(index).map_err(zip_error)?;
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut data)?;
+
+        let data: Arc<[u8]> = data.into();
+        self.extracted.borrow_mut().insert(index, data.clone());
+        Ok(data)
+    }
+}
+
+impl<R: Read + Seek> FileSystem for ZipFileSystem<R> {
+    fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn FileOperations>> {
+        if mode.contains('w') || mode.contains('a') || mode.contains('+') {
+            return Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                "zip archives are read only",
+            ));
+        }
+
+        let index = *self
+            .entries
+            .get(&normalize_path(file_path))
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, file_path.to_string()))?;
+
+        Ok(Box::new(ReadSeekFile::new(Cursor::new(
+            self.extract(index)?,
+        ))))
+    }
+}
+
+fn zip_error(error: ZipError) -> io::Error {
+    match error {
+        ZipError::Io(error) => error,
+        ZipError::FileNotFound => ErrorKind::NotFound.into(),
+        error => io::Error::new(ErrorKind::InvalidData, error),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fs::{FileSystem, ZipFileSystem},
+        scene::{PostProcess, Scene},
+        utils,
+    };
+    use std::io::{Cursor, Write};
+    use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+    fn zip_directory(directory: &str) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(vec![]));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let name = directory.rsplit('/').next().unwrap();
+        writer.add_directory(name, options).unwrap();
+        for entry in std::fs::read_dir(utils::get_model(directory)).unwrap() {
+            let entry = entry.unwrap();
+            let file_name = entry.file_name();
+            writer
+                .start_file(format!("{}/{}", name, file_name.to_string_lossy()), options)
+                .unwrap();
+            writer
+                .write_all(&std::fs::read(entry.path()).unwrap())
+                .unwrap();
+        }
+
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn open_files_in_archive() {
+        let archive = zip_directory("models/GLTF2/BoxTextured-GLTF");
+        let file_system = ZipFileSystem::new(Cursor::new(archive)).unwrap();
+
+        assert!(file_system.contains("BoxTextured-GLTF/BoxTextured.gltf"));
+        assert!(!file_system.contains("BoxTextured-GLTF"));
+
+        let mut file = file_system
+            .open("./BoxTextured-GLTF/textures/../BoxTextured0.bin", "rb")
+            .unwrap();
+        let mut data = vec![0; file.size().unwrap()];
+        let mut read = 0;
+        while read < data.len() {
+            read += file.read(&mut data[read..]).unwrap();
+        }
+
+        let expected = std::fs::read(utils::get_model(
+            "models/GLTF2/BoxTextured-GLTF/BoxTextured0.bin",
+        ))
+        .unwrap();
+        assert_eq!(expected, data);
+        assert!(file_system.open("BoxTextured0.bin", "rb").is_err());
+    }
+
+    #[test]
+    fn import_from_archive() {
+        let archive = zip_directory("models/GLTF2/BoxTextured-GLTF");
+        let mut file_system = ZipFileSystem::new(Cursor::new(archive)).unwrap();
+
+        let scene = Scene::from_file_system(
+            "BoxTextured-GLTF/BoxTextured.gltf",
+            vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ],
+            &mut file_system,
+        )
+        .unwrap();
+
+        assert_eq!(1, scene.meshes.len());
+        assert!(!scene.materials.is_empty());
+    }
+
+    #[test]
+    fn archive_is_read_only() {
+        let archive = zip_directory("models/OBJ");
+        let file_system = ZipFileSystem::new(Cursor::new(archive)).unwrap();
+
+        assert!(file_system.open("OBJ/cube.obj", "rb").is_ok());
+        assert!(file_system.open("OBJ/cube.obj", "wb").is_err());
+    }
+}
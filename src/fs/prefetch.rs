@@ -0,0 +1,88 @@
+use super::{FileOperations, FileSystem, MemoryFileSystem, StdFileSystem};
+use derivative::Derivative;
+use std::{io, path::Path};
+
+/// Reads files with tokio's async IO before an import and hands them to
+/// assimp from memory. Files that weren't prefetched are read from disk.
+///
+/// Assimp reads files on the thread running the import, prefetching keeps
+/// slow reads, e.g. from a network drive, off the blocking thread pool.
+///
+/// ```no_run
+/// # async fn load() -> std::io::Result<()> {
+/// use russimp::fs::AsyncFileSystem;
+///
+/// let mut file_system = AsyncFileSystem::new();
+/// file_system.prefetch("models/OBJ/cube.obj").await?;
+/// file_system.prefetch("models/OBJ/cube.mtl").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default, Derivative)]
+#[derivative(Debug)]
+pub struct AsyncFileSystem {
+    prefetched: MemoryFileSystem,
+    disk: StdFileSystem,
+}
+
+impl AsyncFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the file at `path`, opening `path` later returns its content
+    /// from memory.
+    pub async fn prefetch(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let data = tokio::fs::read(path).await?;
+        self.prefetched.insert(&path.to_string_lossy(), data);
+        Ok(())
+    }
+
+    pub fn is_prefetched(&self, path: &str) -> bool {
+        self.prefetched.contains(path)
+    }
+}
+
+impl FileSystem for AsyncFileSystem {
+    fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn FileOperations>> {
+        if self.prefetched.contains(file_path) {
+            return self.prefetched.open(file_path, mode);
+        }
+
+        self.disk.open(file_path, mode)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fs::{AsyncFileSystem, FileSystem},
+        utils,
+    };
+    use std::io::ErrorKind;
+
+    #[test]
+    fn prefetched_files_are_served_from_memory() {
+        let obj = utils::get_model("models/OBJ/cube.obj");
+        let mtl = utils::get_model("models/OBJ/cube.mtl");
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+
+        let mut file_system = AsyncFileSystem::new();
+        runtime.block_on(file_system.prefetch(&obj)).unwrap();
+
+        assert!(file_system.is_prefetched(&obj));
+        assert!(!file_system.is_prefetched(&mtl));
+        let expected = std::fs::metadata(&obj).unwrap().len() as usize;
+        assert_eq!(
+            expected,
+            file_system.open(&obj, "rb").unwrap().size().unwrap()
+        );
+        assert!(file_system.open(&mtl, "rb").is_ok());
+
+        let missing = runtime.block_on(file_system.prefetch("models/missing.obj"));
+        assert_eq!(ErrorKind::NotFound, missing.err().unwrap().kind());
+    }
+}
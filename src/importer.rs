@@ -0,0 +1,237 @@
+//! The `importer` module combines the options of an import in one builder.
+//!
+//! ```no_run
+//! use russimp::{
+//!     fs::MemoryFileSystem,
+//!     importer::Importer,
+//!     logging::Severity,
+//!     scene::PostProcess,
+//! };
+//!
+//! let mut file_system = MemoryFileSystem::new();
+//! file_system.insert("cube.obj", std::fs::read("models/OBJ/cube.obj").unwrap());
+//!
+//! let scene = Importer::new()
+//!     .post_process(vec![PostProcess::Triangulate])
+//!     .file_system(&file_system)
+//!     .log_sink(|message| {
+//!         if message.severity >= Severity::Warn {
+//!             eprintln!("{}", message.message);
+//!         }
+//!     })
+//!     .progress(&mut |progress: f32| {
+//!         println!("{:.0}%", progress * 100.0);
+//!         true
+//!     })
+//!     .read_file("cube.obj");
+//! ```
+use crate::{
+    fs::{FileSystem, StdFileSystem},
+    logging::{self, LogMessage},
+    progress::{self, ProgressFileSystem, ProgressHandler},
+    property::PropertyStore,
+    scene::{PostProcessFlags, RawScene, Scene},
+    Russult,
+};
+use derivative::Derivative;
+use std::path::Path;
+
+enum Properties<'a> {
+    Owned(PropertyStore),
+    Borrowed(&'a PropertyStore),
+}
+
+impl<'a> Properties<'a> {
+    fn get(&self) -> &PropertyStore {
+        match self {
+            Properties::Owned(props) => props,
+            Properties::Borrowed(props) => props,
+        }
+    }
+}
+
+type LogSink<'a> = Box<dyn FnMut(&LogMessage) + 'a>;
+
+/// Configures an import. Every option is optional, the importer can be used
+/// for more than one file.
+#[derive(Default, Derivative)]
+#[derivative(Debug)]
+pub struct Importer<'a> {
+    flags: PostProcessFlags,
+    #[derivative(Debug = "ignore")]
+    properties: Option<Properties<'a>>,
+    #[derivative(Debug = "ignore")]
+    file_system: Option<&'a dyn FileSystem>,
+    #[derivative(Debug = "ignore")]
+    log_sink: Option<LogSink<'a>>,
+    #[derivative(Debug = "ignore")]
+    progress: Option<&'a mut dyn ProgressHandler>,
+}
+
+impl<'a> Importer<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The post processing steps to run after the import, either
+    /// [`PostProcessFlags`] or a `Vec<PostProcess>`. They are checked with
+    /// [`PostProcessFlags::validate`] before anything is imported.
+    pub fn post_process(mut self, flags: impl Into<PostProcessFlags>) -> Self {
+        self.flags = flags.into();
+        self
+    }
+
+    /// Configuration for the importers and post processing steps.
+    pub fn properties(mut self, properties: PropertyStore) -> Self {
+        self.properties = Some(Properties::Owned(properties));
+        self
+    }
+
+    pub(crate) fn borrowed_properties(mut self, properties: &'a PropertyStore) -> Self {
+        self.properties = Some(Properties::Borrowed(properties));
+        self
+    }
+
+    /// Opens the imported file and the files it references through
+    /// `file_system` instead of from disk. Only [`Importer::read_file`] uses
+    /// it, assimp can't take a file system for imports from memory.
+    pub fn file_system(mut self, file_system: &'a dyn FileSystem) -> Self {
+        self.file_system = Some(file_system);
+        self
+    }
+
+    /// Hands the messages assimp logs during an import to `sink`, see
+    /// [`logging::capture`].
+    pub fn log_sink(mut self, sink: impl FnMut(&LogMessage) + 'a) -> Self {
+        self.log_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Reports the progress of an import to `handler`, which can cancel it,
+    /// see the [`progress`](crate::progress) module.
+    pub fn progress(mut self, handler: &'a mut dyn ProgressHandler) -> Self {
+        self.progress = Some(handler);
+        self
+    }
+
+    pub fn read_file<P: AsRef<Path>>(&mut self, file_path: P) -> Russult<Scene> {
+        self.read_file_raw(file_path)?.convert()
+    }
+
+    /// Imports a file without converting it, e.g. to convert it on another
+    /// thread.
+    pub(crate) fn read_file_raw<P: AsRef<Path>>(&mut self, file_path: P) -> Russult<RawScene> {
+        self.flags.validate()?;
+        let file_path = file_path.as_ref();
+        let flags = self.flags.bits();
+        let props = self.properties.as_ref().map(Properties::get);
+        let file_system = self.file_system;
+        let tracked = self.progress.is_some();
+
+        Self::run(&mut self.log_sink, &mut self.progress, flags, || {
+            if !tracked {
+                return Scene::import_file(file_path, flags, file_system, props);
+            }
+
+            // the reads of the imported file count towards the progress
+            let std_file_system = StdFileSystem::default();
+            let tracked_path = file_path.to_string_lossy();
+            let file_system =
+                ProgressFileSystem::new(file_system.unwrap_or(&std_file_system), &tracked_path);
+            Scene::import_file(file_path, flags, Some(&file_system), props)
+        })
+    }
+
+    /// Imports a file from `buffer`. `hint` is the extension of the file
+    /// format, for formats assimp can't detect from the content.
+    ///
+    /// Files the buffer references are opened from disk, the
+    /// [`file_system`](Importer::file_system) isn't used.
+    pub fn read_memory(&mut self, buffer: &[u8], hint: &str) -> Russult<Scene> {
+        self.flags.validate()?;
+        let flags = self.flags.bits();
+        let props = self.properties.as_ref().map(Properties::get);
+
+        Self::run(&mut self.log_sink, &mut self.progress, flags, || {
+            Scene::import_memory(buffer, flags, hint, props)
+        })?
+        .convert()
+    }
+
+    fn run(
+        log_sink: &mut Option<LogSink<'a>>,
+        progress: &mut Option<&'a mut dyn ProgressHandler>,
+        flags: u32,
+        import: impl FnOnce() -> Russult<RawScene>,
+    ) -> Russult<RawScene> {
+        let import = || match progress {
+            Some(handler) => progress::track(&mut **handler, flags.count_ones() as usize, import),
+            None => import(),
+        };
+
+        match log_sink {
+            Some(sink) => logging::capture(sink, import),
+            None => import(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fs::MemoryFileSystem, importer::Importer, logging::Severity, property::PropertyStore,
+        scene::PostProcess, utils, RussimpError,
+    };
+
+    #[test]
+    fn read_file_with_every_option() {
+        let mut file_system = MemoryFileSystem::new();
+        for name in ["cube.obj", "cube.mtl"] {
+            let data = std::fs::read(utils::get_model(&format!("models/OBJ/{}", name))).unwrap();
+            file_system.insert(name, data);
+        }
+
+        let mut messages = vec![];
+        let mut updates = vec![];
+        let scene = Importer::new()
+            .post_process(vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ])
+            .properties(PropertyStore::default())
+            .file_system(&file_system)
+            .log_sink(|message| messages.push(message.clone()))
+            .progress(&mut |progress: f32| {
+                updates.push(progress);
+                true
+            })
+            .read_file("cube.obj")
+            .unwrap();
+
+        assert_eq!(2, scene.materials.len());
+        assert!(messages
+            .iter()
+            .any(|message| message.severity == Severity::Info));
+        assert_eq!(Some(&1.0), updates.last());
+    }
+
+    #[test]
+    fn read_memory() {
+        let data = std::fs::read(utils::get_model("models/OBJ/cube.obj")).unwrap();
+        let mut importer = Importer::new().post_process(vec![PostProcess::Triangulate]);
+
+        let first = importer.read_memory(&data, "obj").unwrap();
+        let second = importer.read_memory(&data, "obj").unwrap();
+
+        assert_eq!(first.meshes.len(), second.meshes.len());
+    }
+
+    #[test]
+    fn invalid_hints_are_rejected() {
+        let result = Importer::new().read_memory(b"", "o\0bj");
+
+        assert!(matches!(result, Err(RussimpError::InvalidPath(_))));
+    }
+}
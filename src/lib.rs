@@ -8,12 +8,17 @@ mod impl_mint;
 #[cfg(feature = "mint")]
 pub use impl_mint::*;
 
+use animation::Quaternion;
 use derivative::Derivative;
 use std::{
+    convert::Infallible,
     error::Error,
     ffi::IntoStringError,
     fmt,
     fmt::{Display, Formatter},
+    io,
+    ops::{Add, Mul, Sub},
+    path::PathBuf,
     str::Utf8Error,
 };
 use sys::{aiAABB, aiColor3D, aiColor4D, aiMatrix4x4, aiVector2D, aiVector3D};
@@ -22,36 +27,120 @@ use sys::{aiAABB, aiColor3D, aiColor4D, aiMatrix4x4, aiVector2D, aiVector3D};
 extern crate num_derive;
 
 pub mod animation;
+#[cfg(feature = "async")]
+pub mod async_import;
+#[cfg(feature = "rayon")]
+pub mod batch;
 pub mod bone;
 pub mod camera;
+pub mod config;
 pub mod face;
+pub mod formats;
 pub mod fs;
+pub mod importer;
 pub mod light;
+pub mod logging;
 pub mod material;
+pub mod memory;
 pub mod mesh;
 pub mod metadata;
 pub mod node;
+pub mod progress;
 pub mod property;
+pub mod report;
+pub mod retarget;
 pub mod scene;
+pub mod session;
+pub mod validate;
+pub mod version;
+
+pub use version::version;
 
 #[derive(Derivative)]
 #[derivative(Debug)]
+#[non_exhaustive]
 pub enum RussimpError {
-    Import(String),
-    MetadataError(String),
-    MeterialError(String),
-    Primitive(String),
+    /// Assimp failed to import the scene.
+    Import {
+        message: String,
+        /// The imported file, `None` for imports from memory.
+        path: Option<PathBuf>,
+    },
+    /// A metadata entry has an unknown type or no data.
+    Metadata(String),
+    /// A material property has an unknown type or doesn't match its length.
+    Material(String),
+    /// Assimp returned a string that isn't valid UTF-8.
+    Utf8(Utf8Error),
     TextureNotFound,
+    /// The scene has the [`SceneFlags::INCOMPLETE`](scene::SceneFlags::INCOMPLETE)
+    /// flag, see [`Scene::ensure_complete`](scene::Scene::ensure_complete).
+    IncompleteScene,
+    /// A [`ProgressHandler`](progress::ProgressHandler) cancelled the import.
+    Cancelled,
+    /// Reading from a [`FileSystem`](fs::FileSystem) failed.
+    Io(io::Error),
+    /// A path or format hint contains a NUL byte, or a path isn't valid UTF-8
+    /// on a platform other than unix. Assimp can take neither.
+    InvalidPath(String),
+    /// Assimp returned data that can't be converted, e.g. a null pointer in
+    /// a list of meshes.
+    InvalidData(String),
+    /// The requested post processing steps can't run together, see
+    /// [`PostProcessFlags::validate`](scene::PostProcessFlags::validate).
+    PostProcess(String),
+    /// An argument is out of range, e.g. a frame rate of 0 for
+    /// [`Animation::resample`](animation::Animation::resample).
+    InvalidArgument(String),
 }
 
 impl Display for RussimpError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            RussimpError::Import(content) => {
-                write!(f, "{}", content)
+            RussimpError::Import {
+                message,
+                path: Some(path),
+            } => {
+                write!(f, "failed to import {}: {}", path.display(), message)
+            }
+            RussimpError::Import {
+                message,
+                path: None,
+            } => {
+                write!(f, "failed to import: {}", message)
+            }
+            RussimpError::Metadata(content) => {
+                write!(f, "invalid metadata: {}", content)
+            }
+            RussimpError::Material(content) => {
+                write!(f, "invalid material: {}", content)
+            }
+            RussimpError::Utf8(_) => {
+                write!(f, "assimp returned a string that isn't valid UTF-8")
+            }
+            RussimpError::TextureNotFound => {
+                write!(f, "the texture was not found")
+            }
+            RussimpError::IncompleteScene => {
+                write!(f, "the imported scene is incomplete")
+            }
+            RussimpError::Cancelled => {
+                write!(f, "the import was cancelled")
             }
-            _ => {
-                write!(f, "unknown error")
+            RussimpError::Io(_) => {
+                write!(f, "failed to read from the file system")
+            }
+            RussimpError::InvalidPath(path) => {
+                write!(f, "{:?} can't be passed to assimp", path)
+            }
+            RussimpError::InvalidData(content) => {
+                write!(f, "invalid scene data: {}", content)
+            }
+            RussimpError::PostProcess(content) => {
+                write!(f, "invalid post processing steps: {}", content)
+            }
+            RussimpError::InvalidArgument(content) => {
+                write!(f, "invalid argument: {}", content)
             }
         }
     }
@@ -158,6 +247,207 @@ impl From<&aiMatrix4x4> for Matrix4x4 {
     }
 }
 
+impl Matrix4x4 {
+    pub fn identity() -> Self {
+        Self::from_rows([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    fn from_rows(rows: [[f32; 4]; 4]) -> Self {
+        Self {
+            a1: rows[0][0],
+            a2: rows[0][1],
+            a3: rows[0][2],
+            a4: rows[0][3],
+            b1: rows[1][0],
+            b2: rows[1][1],
+            b3: rows[1][2],
+            b4: rows[1][3],
+            c1: rows[2][0],
+            c2: rows[2][1],
+            c3: rows[2][2],
+            c4: rows[2][3],
+            d1: rows[3][0],
+            d2: rows[3][1],
+            d3: rows[3][2],
+            d4: rows[3][3],
+        }
+    }
+
+    fn rows(&self) -> [[f32; 4]; 4] {
+        [
+            [self.a1, self.a2, self.a3, self.a4],
+            [self.b1, self.b2, self.b3, self.b4],
+            [self.c1, self.c2, self.c3, self.c4],
+            [self.d1, self.d2, self.d3, self.d4],
+        ]
+    }
+
+    pub fn transform_point(&self, point: &Vector3D) -> Vector3D {
+        self.transform_vector(point) + Vector3D::new(self.a4, self.b4, self.c4)
+    }
+
+    /// Like [`Matrix4x4::transform_point`], but ignoring the translation.
+    pub fn transform_vector(&self, vector: &Vector3D) -> Vector3D {
+        Vector3D::new(
+            self.a1 * vector.x + self.a2 * vector.y + self.a3 * vector.z,
+            self.b1 * vector.x + self.b2 * vector.y + self.b3 * vector.z,
+            self.c1 * vector.x + self.c2 * vector.y + self.c3 * vector.z,
+        )
+    }
+
+    /// Builds a transformation that scales, then rotates, then translates,
+    /// the inverse of [`Matrix4x4::decompose`].
+    pub fn compose(scaling: Vector3D, rotation: Quaternion, position: Vector3D) -> Self {
+        let rotation = rotation.to_matrix();
+
+        Self::from_rows([
+            [
+                rotation.a1 * scaling.x,
+                rotation.a2 * scaling.y,
+                rotation.a3 * scaling.z,
+                position.x,
+            ],
+            [
+                rotation.b1 * scaling.x,
+                rotation.b2 * scaling.y,
+                rotation.b3 * scaling.z,
+                position.y,
+            ],
+            [
+                rotation.c1 * scaling.x,
+                rotation.c2 * scaling.y,
+                rotation.c3 * scaling.z,
+                position.z,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Splits the transformation into scaling, rotation and position, like
+    /// `aiMatrix4x4::Decompose`.
+    pub fn decompose(&self) -> (Vector3D, Quaternion, Vector3D) {
+        let position = Vector3D::new(self.a4, self.b4, self.c4);
+
+        let columns = [
+            Vector3D::new(self.a1, self.b1, self.c1),
+            Vector3D::new(self.a2, self.b2, self.c2),
+            Vector3D::new(self.a3, self.b3, self.c3),
+        ];
+        let mut scaling = Vector3D::new(
+            columns[0].length(),
+            columns[1].length(),
+            columns[2].length(),
+        );
+
+        // a negative determinant means the transformation mirrors
+        if self.determinant() < 0.0 {
+            scaling = scaling * -1.0;
+        }
+
+        let unscale = |column: Vector3D, scale: f32| {
+            if scale == 0.0 {
+                column
+            } else {
+                column * (1.0 / scale)
+            }
+        };
+        let x = unscale(columns[0], scaling.x);
+        let y = unscale(columns[1], scaling.y);
+        let z = unscale(columns[2], scaling.z);
+        let rotation = Quaternion::from_matrix(&Self::from_rows([
+            [x.x, y.x, z.x, 0.0],
+            [x.y, y.y, z.y, 0.0],
+            [x.z, y.z, z.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]));
+
+        (scaling, rotation, position)
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let rows = self.rows();
+        // 3x3 determinant of the lower rows without the given column
+        let minor = |skip: usize| {
+            let [r0, r1, r2] = [rows[1], rows[2], rows[3]].map(|row| {
+                let mut kept = [0.0; 3];
+                for (value, column) in kept.iter_mut().zip((0..4).filter(|&c| c != skip)) {
+                    *value = row[column];
+                }
+                kept
+            });
+
+            r0[0] * (r1[1] * r2[2] - r1[2] * r2[1]) - r0[1] * (r1[0] * r2[2] - r1[2] * r2[0])
+                + r0[2] * (r1[0] * r2[1] - r1[1] * r2[0])
+        };
+
+        rows[0][0] * minor(0) - rows[0][1] * minor(1) + rows[0][2] * minor(2)
+            - rows[0][3] * minor(3)
+    }
+
+    /// Returns the inverse transformation, or `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<Matrix4x4> {
+        // Gauss-Jordan elimination with partial pivoting
+        let mut rows = self.rows();
+        let mut inverse = Self::identity().rows();
+
+        for column in 0..4 {
+            let pivot = (column..4)
+                .max_by(|&a, &b| rows[a][column].abs().total_cmp(&rows[b][column].abs()))
+                .unwrap_or(column);
+            if rows[pivot][column].abs() <= f32::EPSILON {
+                return None;
+            }
+
+            rows.swap(column, pivot);
+            inverse.swap(column, pivot);
+
+            let factor = 1.0 / rows[column][column];
+            for index in 0..4 {
+                rows[column][index] *= factor;
+                inverse[column][index] *= factor;
+            }
+
+            for row in 0..4 {
+                if row == column {
+                    continue;
+                }
+
+                let factor = rows[row][column];
+                for index in 0..4 {
+                    rows[row][index] -= factor * rows[column][index];
+                    inverse[row][index] -= factor * inverse[column][index];
+                }
+            }
+        }
+
+        Some(Self::from_rows(inverse))
+    }
+}
+
+impl Mul for Matrix4x4 {
+    type Output = Matrix4x4;
+
+    fn mul(self, rhs: Matrix4x4) -> Self::Output {
+        let (lhs, rhs) = (self.rows(), rhs.rows());
+        let mut result = [[0.0; 4]; 4];
+
+        for (row, result_row) in result.iter_mut().enumerate() {
+            for (column, value) in result_row.iter_mut().enumerate() {
+                *value = (0..4)
+                    .map(|index| lhs[row][index] * rhs[index][column])
+                    .sum();
+            }
+        }
+
+        Self::from_rows(result)
+    }
+}
+
 #[derive(Clone, Copy, Default, Derivative)]
 #[derivative(Debug)]
 #[repr(C)]
@@ -175,7 +465,15 @@ impl From<&aiVector2D> for Vector2D {
     }
 }
 
-impl Error for RussimpError {}
+impl Error for RussimpError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RussimpError::Utf8(error) => Some(error),
+            RussimpError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Clone, Copy, Default, Derivative, PartialEq)]
 #[derivative(Debug)]
@@ -196,34 +494,121 @@ impl From<&aiVector3D> for Vector3D {
     }
 }
 
+impl Vector3D {
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn dot(&self, rhs: &Vector3D) -> f32 {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn cross(&self, rhs: &Vector3D) -> Vector3D {
+        Vector3D::new(
+            self.y * rhs.z - self.z * rhs.y,
+            self.z * rhs.x - self.x * rhs.z,
+            self.x * rhs.y - self.y * rhs.x,
+        )
+    }
+
+    pub fn length(&self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the vector scaled to unit length, or the vector itself if its
+    /// length is zero.
+    pub fn normalize(&self) -> Vector3D {
+        let length = self.length();
+        if length == 0.0 {
+            return *self;
+        }
+
+        *self * (1.0 / length)
+    }
+}
+
+impl Add for Vector3D {
+    type Output = Vector3D;
+
+    fn add(self, rhs: Vector3D) -> Self::Output {
+        Vector3D::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vector3D {
+    type Output = Vector3D;
+
+    fn sub(self, rhs: Vector3D) -> Self::Output {
+        Vector3D::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f32> for Vector3D {
+    type Output = Vector3D;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Vector3D::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
 impl From<Utf8Error> for RussimpError {
     fn from(val: Utf8Error) -> Self {
-        RussimpError::Primitive(val.to_string())
+        RussimpError::Utf8(val)
     }
 }
 
 impl From<IntoStringError> for RussimpError {
     fn from(val: IntoStringError) -> Self {
-        RussimpError::Primitive(val.to_string())
+        RussimpError::Utf8(val.utf8_error())
+    }
+}
+
+impl From<Infallible> for RussimpError {
+    fn from(val: Infallible) -> Self {
+        match val {}
+    }
+}
+
+impl From<io::Error> for RussimpError {
+    fn from(val: io::Error) -> Self {
+        RussimpError::Io(val)
     }
 }
 
 pub type Russult<T> = Result<T, RussimpError>;
 
 mod utils {
-    use std::{os::raw::c_uint, ptr::slice_from_raw_parts};
+    use crate::{RussimpError, Russult};
+    use std::{
+        ffi::CStr,
+        os::raw::{c_char, c_uint},
+        ptr::slice_from_raw_parts,
+    };
+
+    /// Copies a C string owned by assimp, an empty string for null.
+    pub(crate) fn get_string(ptr: *const c_char) -> String {
+        if ptr.is_null() {
+            return String::new();
+        }
+
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    }
 
     pub(crate) fn get_base_type_vec_from_raw<'a, TRaw: 'a>(
         data: *mut *mut TRaw,
         len: u32,
-    ) -> Vec<&'a TRaw> {
+    ) -> Russult<Vec<&'a TRaw>> {
         let slice = slice_from_raw_parts(data, len as usize);
         if slice.is_null() {
-            return vec![];
+            return Ok(vec![]);
         }
 
         let raw = unsafe { slice.as_ref() }.unwrap();
-        raw.iter().map(|x| unsafe { x.as_ref() }.unwrap()).collect()
+        raw.iter()
+            .map(|x| unsafe { x.as_ref() }.ok_or_else(null_element::<TRaw>))
+            .collect()
     }
 
     #[allow(dead_code)]
@@ -270,21 +655,35 @@ mod utils {
         raw.to_vec()
     }
 
-    pub(crate) fn get_vec_from_raw<'a, TComponent: From<&'a TRaw>, TRaw: 'a>(
+    pub(crate) fn get_vec_from_raw<'a, TComponent, TRaw: 'a>(
         raw_source: *mut *mut TRaw,
         num_raw_items: c_uint,
-    ) -> Vec<TComponent> {
+    ) -> Russult<Vec<TComponent>>
+    where
+        TComponent: TryFrom<&'a TRaw>,
+        RussimpError: From<TComponent::Error>,
+    {
         let slice = slice_from_raw_parts(raw_source, num_raw_items as usize);
         if slice.is_null() {
-            return vec![];
+            return Ok(vec![]);
         }
 
         let raw = unsafe { slice.as_ref() }.unwrap();
         raw.iter()
-            .map(|x| (unsafe { x.as_ref() }.unwrap()).into())
+            .map(|x| {
+                let raw = unsafe { x.as_ref() }.ok_or_else(null_element::<TRaw>)?;
+                Ok(TComponent::try_from(raw)?)
+            })
             .collect()
     }
 
+    fn null_element<TRaw>() -> RussimpError {
+        RussimpError::InvalidData(format!(
+            "null pointer in a list of {}",
+            std::any::type_name::<TRaw>()
+        ))
+    }
+
     pub(crate) fn get_vec_of_vecs_from_raw<'a, TRaw: 'a, TComponent: From<&'a TRaw>>(
         raw: [*mut TRaw; 8usize],
         len: c_uint,
@@ -294,3 +693,91 @@ mod utils {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::Quaternion, sys::aiVector3D, utils, Matrix4x4, RussimpError, Russult, Vector3D,
+    };
+    use std::{error::Error, io};
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 1e-5,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn matrix_compose_and_decompose() {
+        let rotation = Quaternion::new(0.5, 0.5, 0.5, 0.5);
+        let matrix = Matrix4x4::compose(
+            Vector3D::new(2.0, 3.0, 4.0),
+            rotation,
+            Vector3D::new(1.0, -2.0, 3.0),
+        );
+
+        assert_close(24.0, matrix.determinant());
+
+        let (scaling, decomposed, position) = matrix.decompose();
+        assert_eq!(Vector3D::new(1.0, -2.0, 3.0), position);
+        assert_close(2.0, scaling.x);
+        assert_close(3.0, scaling.y);
+        assert_close(4.0, scaling.z);
+        assert_close(1.0, decomposed.dot(&rotation));
+    }
+
+    #[test]
+    fn matrix_inverse() {
+        let matrix = Matrix4x4::compose(
+            Vector3D::new(2.0, 3.0, 4.0),
+            Quaternion::new(0.5, 0.5, 0.5, 0.5),
+            Vector3D::new(1.0, -2.0, 3.0),
+        );
+
+        let identity = matrix * matrix.inverse().unwrap();
+        for (row, expected_row) in identity.rows().iter().zip(Matrix4x4::identity().rows()) {
+            for (value, expected) in row.iter().zip(expected_row) {
+                assert_close(expected, *value);
+            }
+        }
+
+        assert!(Matrix4x4::default().inverse().is_none());
+    }
+
+    #[test]
+    fn errors_describe_themselves() {
+        let error = RussimpError::Import {
+            message: "Unable to open file".to_string(),
+            path: None,
+        };
+        assert_eq!("failed to import: Unable to open file", error.to_string());
+        assert!(error.source().is_none());
+
+        let error = RussimpError::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+        assert_eq!("failed to read from the file system", error.to_string());
+        assert_eq!("missing", error.source().unwrap().to_string());
+
+        let bytes = std::ffi::CString::new(vec![0xff]).unwrap();
+        let error = RussimpError::from(bytes.into_string().unwrap_err());
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn null_elements_are_rejected() {
+        let mut vector = aiVector3D {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        };
+        let mut elements = [&mut vector as *mut aiVector3D, std::ptr::null_mut()];
+
+        let vectors: Vec<Vector3D> = utils::get_vec_from_raw(elements.as_mut_ptr(), 1).unwrap();
+        assert_eq!(vec![Vector3D::new(1.0, 2.0, 3.0)], vectors);
+
+        let result: Russult<Vec<Vector3D>> = utils::get_vec_from_raw(elements.as_mut_ptr(), 2);
+        assert!(matches!(result, Err(RussimpError::InvalidData(_))));
+    }
+}
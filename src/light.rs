@@ -1,4 +1,4 @@
-use crate::{sys::*, Color3D, Vector2D, Vector3D};
+use crate::{node::Node, sys::*, Color3D, Matrix4x4, Vector2D, Vector3D};
 use derivative::Derivative;
 use num_enum::Default;
 
@@ -42,6 +42,26 @@ impl From<&aiLight> for Light {
     }
 }
 
+impl Light {
+    /// Global transformation of the node with the same name as the light,
+    /// searched below and including `root`.
+    pub fn world_transformation(&self, root: &Node) -> Option<Matrix4x4> {
+        root.global_transformation_of(&self.name)
+    }
+
+    /// [`Light::pos`] transformed into world space.
+    pub fn world_position(&self, root: &Node) -> Option<Vector3D> {
+        self.world_transformation(root)
+            .map(|world| world.transform_point(&self.pos))
+    }
+
+    /// [`Light::direction`] transformed into world space and normalized.
+    pub fn world_direction(&self, root: &Node) -> Option<Vector3D> {
+        self.world_transformation(root)
+            .map(|world| world.transform_vector(&self.direction).normalize())
+    }
+}
+
 #[derive(Derivative, num_enum::IntoPrimitive, num_enum::FromPrimitive, Default, PartialEq)]
 #[derivative(Debug)]
 #[repr(u32)]
@@ -57,7 +77,8 @@ pub enum LightSourceType {
 
 #[cfg(test)]
 mod test {
-    use crate::light::LightSourceType;
+    use crate::{light::LightSourceType, Vector3D};
+    use std::f32::consts::FRAC_1_SQRT_2;
 
     #[test]
     fn light_available() {
@@ -114,6 +135,75 @@ mod test {
         assert_eq!(LightSourceType::Area, scene.lights[2].light_source_type);
     }
 
+    #[test]
+    fn light_world_transformation() {
+        use crate::{
+            scene::{PostProcess, Scene},
+            utils,
+        };
+
+        let current_directory_buf = utils::get_model("models/BLEND/AreaLight_269.blend");
+
+        let scene = Scene::from_file(
+            current_directory_buf.as_str(),
+            vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ],
+        )
+        .unwrap();
+
+        let root = scene.root.as_ref().unwrap();
+        let light = |name: &str| {
+            let light = scene.lights.iter().find(|light| light.name == name);
+            let light = light.unwrap();
+            assert_eq!(name, scene.light_node(light).unwrap().name);
+            light
+        };
+
+        // Baz is a point light at (2, 0, 0), the lights are placed by their nodes
+        let baz = light("Baz");
+        assert_vector(
+            Vector3D::new(2.0, 0.0, 0.0),
+            baz.world_position(root).unwrap(),
+        );
+
+        // Foo is an area light at (0, 0, 2) facing down
+        let foo = light("Foo");
+        assert_vector(
+            Vector3D::new(0.0, 0.0, 2.0),
+            foo.world_position(root).unwrap(),
+        );
+        assert_vector(
+            Vector3D::new(0.0, 0.0, -1.0),
+            foo.world_direction(root).unwrap(),
+        );
+
+        // Bar is an area light at (-3.14, 3, 2) rotated by 45 degrees around x
+        let bar = light("Bar");
+        assert_vector(
+            Vector3D::new(-3.14, 3.0, 2.0),
+            bar.world_position(root).unwrap(),
+        );
+        assert_vector(
+            Vector3D::new(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            bar.world_direction(root).unwrap(),
+        );
+    }
+
+    fn assert_vector(expected: Vector3D, actual: Vector3D) {
+        assert!(
+            (expected.x - actual.x).abs() < 1e-5
+                && (expected.y - actual.y).abs() < 1e-5
+                && (expected.z - actual.z).abs() < 1e-5,
+            "expected {:?} but got {:?}",
+            expected,
+            actual
+        );
+    }
+
     #[test]
     fn debug_light() {
         use crate::{
@@ -0,0 +1,365 @@
+//! The `logging` module forwards assimp's log output to Rust.
+//!
+//! Assimp writes all of its messages to one global logger. Russimp attaches a
+//! single log stream to it the first time a capture starts, and hands every
+//! message to the sink installed on the thread that produced it. Imports run
+//! on the calling thread, so a capture only sees the messages of the imports
+//! made inside it, even while other threads import at the same time.
+//!
+//! ```no_run
+//! use russimp::{logging, scene::{PostProcess, Scene}};
+//!
+//! let mut warnings = vec![];
+//! let scene = logging::capture(
+//!     |message| {
+//!         if message.severity >= logging::Severity::Warn {
+//!             warnings.push(message.message.clone());
+//!         }
+//!     },
+//!     || Scene::from_file("models/BLEND/box.blend", vec![PostProcess::ValidateDataStructure]),
+//! );
+//! ```
+use crate::sys::{aiAttachLogStream, aiEnableVerboseLogging, aiLogStream};
+use derivative::Derivative;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    ffi::CStr,
+    os::raw::c_char,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+    sync::{Mutex, Once, PoisonError},
+};
+
+#[derive(Clone, Copy, Derivative, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derivative(Debug)]
+pub enum Severity {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<Severity> for log::Level {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Debug => log::Level::Debug,
+            Severity::Info => log::Level::Info,
+            Severity::Warn => log::Level::Warn,
+            Severity::Error => log::Level::Error,
+        }
+    }
+}
+
+#[derive(Clone, Derivative, PartialEq)]
+#[derivative(Debug)]
+pub struct LogMessage {
+    pub severity: Severity,
+    /// The message without assimp's severity and thread prefix.
+    pub message: String,
+}
+
+impl LogMessage {
+    /// Splits lines like `Warn,  T0: Some message` written by assimp's
+    /// default logger.
+    fn parse(raw: &str) -> Self {
+        let raw = raw.trim_end();
+        let (severity, rest) = match raw.split_once(',') {
+            Some(("Debug", rest)) => (Severity::Debug, rest),
+            Some(("Info", rest)) => (Severity::Info, rest),
+            Some(("Warn", rest)) => (Severity::Warn, rest),
+            Some(("Error", rest)) => (Severity::Error, rest),
+            _ => {
+                return Self {
+                    severity: Severity::Info,
+                    message: raw.to_string(),
+                }
+            }
+        };
+
+        let rest = rest.trim_start();
+        let message = match rest.split_once(": ") {
+            Some((thread, message)) if thread.starts_with('T') => message,
+            _ => rest,
+        };
+
+        Self {
+            severity,
+            message: message.to_string(),
+        }
+    }
+}
+
+type Sink = dyn FnMut(&LogMessage);
+
+thread_local! {
+    /// Installed sinks, innermost last, and whether they forward messages to
+    /// the sink before them.
+    static SINKS: RefCell<Vec<(*mut Sink, bool)>> = RefCell::new(vec![]);
+    static DISPATCHING: Cell<bool> = const { Cell::new(false) };
+    static SINK_PANIC: RefCell<Option<Box<dyn Any + Send>>> = RefCell::new(None);
+}
+
+static ATTACH_STREAM: Once = Once::new();
+
+/// Whether verbose logging was requested by the user, and the number of
+/// imports that need it for themselves.
+static VERBOSE: Mutex<(bool, usize)> = Mutex::new((false, 0));
+
+/// Makes assimp emit debug messages as well. Has to be enabled before the
+/// import whose messages should be included.
+pub fn set_verbose(verbose: bool) {
+    let mut state = VERBOSE.lock().unwrap_or_else(PoisonError::into_inner);
+    state.0 = verbose;
+    apply_verbose(&state);
+}
+
+/// Enables verbose logging while `f` runs.
+pub(crate) fn verbose<R>(f: impl FnOnce() -> R) -> R {
+    struct VerboseGuard;
+
+    impl Drop for VerboseGuard {
+        fn drop(&mut self) {
+            let mut state = VERBOSE.lock().unwrap_or_else(PoisonError::into_inner);
+            state.1 -= 1;
+            apply_verbose(&state);
+        }
+    }
+
+    {
+        let mut state = VERBOSE.lock().unwrap_or_else(PoisonError::into_inner);
+        state.1 += 1;
+        apply_verbose(&state);
+    }
+
+    let _guard = VerboseGuard;
+    f()
+}
+
+fn apply_verbose(&(requested, scopes): &(bool, usize)) {
+    unsafe {
+        aiEnableVerboseLogging((requested || scopes > 0) as _);
+    }
+}
+
+/// Runs `f` and hands every assimp message logged on this thread meanwhile to
+/// `sink`.
+///
+/// Captures can be nested, in which case the innermost sink receives the
+/// messages. A panic in `sink` is resumed once `f` returns.
+pub fn capture<R>(mut sink: impl FnMut(&LogMessage), f: impl FnOnce() -> R) -> R {
+    install(&mut sink, false, f)
+}
+
+/// Like [`capture`], but the messages are passed on to the enclosing capture
+/// as well. Used by russimp itself so it doesn't hide messages from users.
+pub(crate) fn observe<R>(mut sink: impl FnMut(&LogMessage), f: impl FnOnce() -> R) -> R {
+    install(&mut sink, true, f)
+}
+
+fn install<R>(sink: &mut dyn FnMut(&LogMessage), forward: bool, f: impl FnOnce() -> R) -> R {
+    ATTACH_STREAM.call_once(|| {
+        let stream = aiLogStream {
+            callback: Some(log_callback),
+            user: ptr::null_mut(),
+        };
+
+        // assimp copies the stream, it stays attached for the rest of the
+        // process and dispatches to the thread local sinks
+        unsafe { aiAttachLogStream(&stream) };
+    });
+
+    // SAFETY: the guard removes the pointer again before `sink` is dropped
+    let sink: *mut Sink = unsafe { std::mem::transmute(sink) };
+    SINKS.with(|sinks| sinks.borrow_mut().push((sink, forward)));
+
+    let result = {
+        let _guard = SinkGuard;
+        f()
+    };
+
+    if let Some(payload) = SINK_PANIC.with(|sink_panic| sink_panic.borrow_mut().take()) {
+        panic::resume_unwind(payload);
+    }
+
+    result
+}
+
+/// Runs `f` and forwards the assimp messages it causes to the `log` crate,
+/// with the target `russimp`.
+pub fn forward_to_log<R>(f: impl FnOnce() -> R) -> R {
+    capture(
+        |message| {
+            log::log!(target: "russimp", message.severity.into(), "{}", message.message);
+        },
+        f,
+    )
+}
+
+/// Runs `f` and emits the assimp messages it causes as `tracing` events, with
+/// the target `russimp`.
+#[cfg(feature = "tracing")]
+pub fn forward_to_tracing<R>(f: impl FnOnce() -> R) -> R {
+    capture(
+        |message| match message.severity {
+            Severity::Debug => tracing::debug!(target: "russimp", "{}", message.message),
+            Severity::Info => tracing::info!(target: "russimp", "{}", message.message),
+            Severity::Warn => tracing::warn!(target: "russimp", "{}", message.message),
+            Severity::Error => tracing::error!(target: "russimp", "{}", message.message),
+        },
+        f,
+    )
+}
+
+struct SinkGuard;
+
+impl Drop for SinkGuard {
+    fn drop(&mut self) {
+        SINKS.with(|sinks| sinks.borrow_mut().pop());
+    }
+}
+
+unsafe extern "C" fn log_callback(message: *const c_char, _user: *mut c_char) {
+    if message.is_null() {
+        return;
+    }
+
+    let sinks: Vec<*mut Sink> = SINKS.with(|sinks| {
+        let sinks = sinks.borrow();
+        let receivers = sinks
+            .iter()
+            .rev()
+            .position(|&(_, forward)| !forward)
+            .map_or(sinks.len(), |index| index + 1);
+
+        sinks
+            .iter()
+            .rev()
+            .take(receivers)
+            .map(|&(sink, _)| sink)
+            .collect()
+    });
+    if sinks.is_empty() {
+        return;
+    }
+
+    // a sink that logs through assimp itself must not be re-entered
+    if DISPATCHING.with(|dispatching| dispatching.replace(true)) {
+        return;
+    }
+
+    let message = LogMessage::parse(&CStr::from_ptr(message).to_string_lossy());
+    for sink in sinks {
+        // unwinding into assimp is undefined behaviour
+        let result = panic::catch_unwind(AssertUnwindSafe(|| (*sink)(&message)));
+
+        if let Err(payload) = result {
+            SINK_PANIC.with(|sink_panic| {
+                sink_panic.borrow_mut().get_or_insert(payload);
+            });
+        }
+    }
+    DISPATCHING.with(|dispatching| dispatching.set(false));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        logging::{self, LogMessage, Severity},
+        utils,
+    };
+    use std::ffi::CString;
+
+    fn emit(message: &str) {
+        let message = CString::new(message).unwrap();
+        unsafe { logging::log_callback(message.as_ptr(), std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn parse_log_message() {
+        assert_eq!(
+            LogMessage {
+                severity: Severity::Warn,
+                message: "Some message".to_string()
+            },
+            LogMessage::parse("Warn,  T0: Some message\n")
+        );
+        assert_eq!(
+            LogMessage {
+                severity: Severity::Info,
+                message: "no prefix".to_string()
+            },
+            LogMessage::parse("no prefix")
+        );
+    }
+
+    #[test]
+    fn nested_captures_receive_their_own_messages() {
+        let mut outer = vec![];
+        let mut inner = vec![];
+
+        logging::capture(
+            |message| outer.push(message.message.clone()),
+            || {
+                emit("Info,  T0: first");
+                logging::capture(
+                    |message| inner.push(message.message.clone()),
+                    || emit("Error, T0: second"),
+                );
+                emit("Debug, T0: third");
+            },
+        );
+        emit("Info,  T0: dropped");
+
+        assert_eq!(vec!["first", "third"], outer);
+        assert_eq!(vec!["second"], inner);
+    }
+
+    #[test]
+    fn observers_forward_messages() {
+        let mut captured = vec![];
+        let mut observed = vec![];
+
+        logging::capture(
+            |message| captured.push(message.message.clone()),
+            || {
+                logging::observe(
+                    |message| observed.push(message.message.clone()),
+                    || emit("Warn,  T0: warning"),
+                )
+            },
+        );
+
+        assert_eq!(vec!["warning"], captured);
+        assert_eq!(vec!["warning"], observed);
+    }
+
+    #[test]
+    #[should_panic(expected = "sink panicked")]
+    fn sink_panics_are_resumed() {
+        logging::capture(|_| panic!("sink panicked"), || emit("Info,  T0: message"));
+    }
+
+    #[test]
+    fn capture_import_errors() {
+        use crate::scene::{PostProcess, Scene};
+
+        let current_directory_buf = utils::get_model("models/missing.blend");
+
+        let mut messages = vec![];
+        let scene = logging::capture(
+            |message| messages.push(message.clone()),
+            || {
+                Scene::from_file(
+                    current_directory_buf.as_str(),
+                    vec![PostProcess::ValidateDataStructure],
+                )
+            },
+        );
+
+        assert!(scene.is_err());
+        assert!(messages
+            .iter()
+            .any(|message| message.severity == Severity::Error));
+    }
+}
@@ -1,7 +1,6 @@
 use crate::{sys::*, utils, utils::get_base_type_vec_from_raw, RussimpError, Russult};
 use derivative::Derivative;
 use num_enum::TryFromPrimitive;
-use num_traits::FromPrimitive;
 use std::{
     cell::RefCell, collections::HashMap, ffi::CStr, mem::MaybeUninit, path::Path,
     ptr::slice_from_raw_parts, rc::Rc,
@@ -84,9 +83,9 @@ pub enum DataContent {
 }
 
 pub(crate) fn generate_materials(scene: &aiScene) -> Russult<Vec<Material>> {
-    let textures = get_base_type_vec_from_raw(scene.mTextures, scene.mNumTextures);
-    let materials = get_base_type_vec_from_raw(scene.mMaterials, scene.mNumMaterials);
-    let properties = create_material_properties(&materials);
+    let textures = get_base_type_vec_from_raw(scene.mTextures, scene.mNumTextures)?;
+    let materials = get_base_type_vec_from_raw(scene.mMaterials, scene.mNumMaterials)?;
+    let properties = create_material_properties(&materials)?;
     let mut result = Vec::new();
 
     let mut converted_textures: HashMap<usize, Rc<RefCell<Texture>>> = HashMap::new();
@@ -104,7 +103,7 @@ pub(crate) fn generate_materials(scene: &aiScene) -> Russult<Vec<Material>> {
                     if let Some(tex) = converted_textures.get(&embedded_texture) {
                         material_textures.insert(tex_type, tex.clone());
                     } else {
-                        let new_texture = create_texture_from(textures[embedded_texture], true);
+                        let new_texture = create_texture_from(textures[embedded_texture], true)?;
                         converted_textures
                             .insert(embedded_texture, Rc::new(RefCell::new(new_texture)));
                         material_textures.insert(
@@ -177,16 +176,20 @@ fn get_texture_filename(
     Err(RussimpError::TextureNotFound)
 }
 
-fn create_texture_from(texture: &aiTexture, is_embedded: bool) -> Texture {
-    let ach_format_hint = unsafe { CStr::from_ptr(texture.achFormatHint.as_ptr()) }
-        .to_str()
-        .unwrap()
+fn create_texture_from(texture: &aiTexture, is_embedded: bool) -> Russult<Texture> {
+    let ach_format_hint = texture.achFormatHint.map(|c| c as u8);
+    let ach_format_hint = CStr::from_bytes_until_nul(&ach_format_hint)
+        .map_err(|_| RussimpError::InvalidData("unterminated texture format hint".to_string()))?
+        .to_str()?
         .to_string();
 
     let data = if is_embedded {
         let compressed_bytes =
             slice_from_raw_parts(texture.pcData as *const u8, texture.mWidth as usize);
-        DataContent::Bytes(unsafe { compressed_bytes.as_ref() }.unwrap().to_vec())
+        let compressed_bytes = unsafe { compressed_bytes.as_ref() }.ok_or_else(|| {
+            RussimpError::InvalidData("embedded texture without data".to_string())
+        })?;
+        DataContent::Bytes(compressed_bytes.to_vec())
     } else {
         DataContent::Texel(utils::get_vec(
             texture.pcData,
@@ -194,19 +197,19 @@ fn create_texture_from(texture: &aiTexture, is_embedded: bool) -> Texture {
         ))
     };
 
-    Texture {
+    Ok(Texture {
         height: texture.mHeight,
         width: texture.mWidth,
         filename: texture.mFilename.into(),
         ach_format_hint,
         data,
-    }
+    })
 }
 
 fn get_embedded_texture(file_name: &str, textures: &Vec<&aiTexture>) -> Option<usize> {
     if file_name.starts_with(EMBEDDED_TEXNAME_PREFIX) {
         let temp = file_name.split_at(1).1.to_string();
-        let index = temp.parse::<usize>().unwrap();
+        let index = temp.parse::<usize>().ok()?;
         if textures.len() <= index {
             return None;
         }
@@ -233,28 +236,28 @@ fn get_embedded_texture(file_name: &str, textures: &Vec<&aiTexture>) -> Option<u
     None
 }
 
-fn create_material_properties(materials: &Vec<&aiMaterial>) -> Vec<Vec<MaterialProperty>> {
+fn create_material_properties(materials: &Vec<&aiMaterial>) -> Russult<Vec<Vec<MaterialProperty>>> {
     let mut material_properties = Vec::new();
 
     for &i in materials {
-        let properties = get_properties(i);
+        let properties = get_properties(i)?;
 
         material_properties.push(properties);
     }
 
-    material_properties
+    Ok(material_properties)
 }
 
-fn get_properties(material: &aiMaterial) -> Vec<MaterialProperty> {
-    let properties = get_base_type_vec_from_raw(material.mProperties, material.mNumProperties);
+fn get_properties(material: &aiMaterial) -> Russult<Vec<MaterialProperty>> {
+    let properties = get_base_type_vec_from_raw(material.mProperties, material.mNumProperties)?;
     let mut result = Vec::new();
 
     for item in properties {
-        let material_property = MaterialProperty::new(material, item);
+        let material_property = MaterialProperty::new(material, item)?;
         result.push(material_property);
     }
 
-    result
+    Ok(result)
 }
 
 #[derive(Derivative, Clone)]
@@ -356,7 +359,7 @@ impl<'a> MaterialPropertyCaster for IntegerPropertyContent<'a> {
         }
 
         let key_string: String = self.key.into();
-        Err(RussimpError::MeterialError(format!(
+        Err(RussimpError::Material(format!(
             "Error while parsing {} to f32",
             key_string
         )))
@@ -394,7 +397,7 @@ impl<'a> MaterialPropertyCaster for FloatPropertyContent<'a> {
         }
 
         let key_string: String = self.key.into();
-        Err(RussimpError::MeterialError(format!(
+        Err(RussimpError::Material(format!(
             "Error while parsing {} to f32",
             key_string
         )))
@@ -423,7 +426,7 @@ impl<'a> MaterialPropertyCaster for StringPropertyContent<'a> {
         }
 
         let key_string: String = self.key.into();
-        Err(RussimpError::MeterialError(format!(
+        Err(RussimpError::Material(format!(
             "Error while parsing {} to string",
             key_string
         )))
@@ -448,7 +451,8 @@ impl MaterialProperty {
     ) -> Russult<PropertyTypeInfo> {
         let slice =
             slice_from_raw_parts(property.mData as *const u8, property.mDataLength as usize);
-        let data = unsafe { slice.as_ref() }.unwrap();
+        let data = unsafe { slice.as_ref() }
+            .ok_or_else(|| RussimpError::Material("property without data".to_string()))?;
 
         let casters: Vec<Box<dyn MaterialPropertyCaster>> = vec![
             Box::new(StringPropertyContent {
@@ -487,20 +491,23 @@ impl MaterialProperty {
             }
         }
 
-        Err(RussimpError::MeterialError(
+        Err(RussimpError::Material(
             "could not find caster for property type".to_string(),
         ))
     }
 
-    pub fn new(material: &aiMaterial, property: &aiMaterialProperty) -> MaterialProperty {
-        let data = Self::try_get_data_from_property(material, property).unwrap();
+    pub fn new(material: &aiMaterial, property: &aiMaterialProperty) -> Russult<MaterialProperty> {
+        let data = Self::try_get_data_from_property(material, property)?;
+        let semantic = TextureType::try_from(property.mSemantic).map_err(|_| {
+            RussimpError::Material(format!("unknown texture type {}", property.mSemantic))
+        })?;
 
-        MaterialProperty {
+        Ok(MaterialProperty {
             key: property.mKey.into(),
             data,
             index: property.mIndex as usize,
-            semantic: FromPrimitive::from_u32(property.mSemantic).unwrap(),
-        }
+            semantic,
+        })
     }
 }
 
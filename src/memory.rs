@@ -0,0 +1,385 @@
+//! The `memory` module measures how much memory a scene takes up, both in
+//! assimp while it is imported and as a russimp [`Scene`]. Assimp's numbers
+//! are part of the [`ImportReport`](crate::report::ImportReport).
+//!
+//! ```no_run
+//! use russimp::scene::{PostProcess, Scene};
+//!
+//! let (scene, report) =
+//!     Scene::from_file_with_report("models/FBX/y_bot_run.fbx", vec![PostProcess::Triangulate])
+//!         .unwrap();
+//! println!("assimp: {:?}", report.native_memory);
+//! println!("russimp: {:?}", scene.memory_usage());
+//! ```
+use crate::{
+    animation::{
+        Animation, MeshAnim, MeshKey, MeshMorphAnim, MeshMorphKey, NodeAnim, QuatKey, VectorKey,
+    },
+    bone::{Bone, VertexWeight},
+    camera::Camera,
+    face::Face,
+    light::Light,
+    material::{DataContent, Material, MaterialProperty, PropertyTypeInfo, Texel, Texture},
+    mesh::{AnimMesh, Mesh},
+    metadata::{MetaData, MetaDataEntry, MetadataType},
+    node::Node,
+    scene::Scene,
+    sys::{aiGetMemoryRequirements, aiMemoryInfo, aiScene},
+    Color4D, Vector3D,
+};
+use derivative::Derivative;
+use std::{cell::RefCell, collections::HashSet, mem::size_of, rc::Rc};
+
+/// Bytes used by every part of a scene.
+#[derive(Clone, Copy, Default, Derivative, PartialEq, Eq)]
+#[derivative(Debug)]
+pub struct MemoryInfo {
+    pub textures: usize,
+    pub materials: usize,
+    pub meshes: usize,
+    pub nodes: usize,
+    pub animations: usize,
+    pub cameras: usize,
+    pub lights: usize,
+    pub total: usize,
+}
+
+impl From<&aiMemoryInfo> for MemoryInfo {
+    fn from(info: &aiMemoryInfo) -> Self {
+        Self {
+            textures: info.textures as _,
+            materials: info.materials as _,
+            meshes: info.meshes as _,
+            nodes: info.nodes as _,
+            animations: info.animations as _,
+            cameras: info.cameras as _,
+            lights: info.lights as _,
+            total: info.total as _,
+        }
+    }
+}
+
+impl MemoryInfo {
+    /// Asks assimp for the memory `scene` takes up.
+    pub(crate) fn native(scene: &aiScene) -> Self {
+        let mut info = aiMemoryInfo {
+            textures: 0,
+            materials: 0,
+            meshes: 0,
+            nodes: 0,
+            animations: 0,
+            cameras: 0,
+            lights: 0,
+            total: 0,
+        };
+        unsafe { aiGetMemoryRequirements(scene, &mut info) };
+        (&info).into()
+    }
+}
+
+impl Scene {
+    /// Estimates the memory the converted scene takes up, to compare it with
+    /// [`ImportReport::native_memory`](crate::report::ImportReport::native_memory).
+    ///
+    /// Counts the scene's own allocations from their capacity, without the
+    /// bookkeeping of the allocator. Textures shared between materials are
+    /// counted once.
+    pub fn memory_usage(&self) -> MemoryInfo {
+        let mut textures = HashSet::new();
+        let textures = self
+            .materials
+            .iter()
+            .flat_map(|material| material.textures.values())
+            .filter(|texture| textures.insert(Rc::as_ptr(texture)))
+            .map(|texture| rc_size::<RefCell<Texture>>() + texture.borrow().heap_size())
+            .sum();
+
+        let mut info = MemoryInfo {
+            textures,
+            materials: self.materials.heap_size(),
+            meshes: self.meshes.heap_size(),
+            nodes: self
+                .root
+                .as_ref()
+                .map_or(0, |root| rc_size::<Node>() + root.heap_size()),
+            animations: self.animations.heap_size(),
+            cameras: self.cameras.heap_size(),
+            lights: self.lights.heap_size(),
+            total: 0,
+        };
+        info.total = info.textures
+            + info.materials
+            + info.meshes
+            + info.nodes
+            + info.animations
+            + info.cameras
+            + info.lights;
+        info
+    }
+}
+
+/// The size of an `Rc` allocation, which holds both reference counts.
+fn rc_size<T>() -> usize {
+    2 * size_of::<usize>() + size_of::<T>()
+}
+
+/// Memory a value owns outside of itself.
+trait HeapSize {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+macro_rules! no_heap {
+    ($($ty:ty),*) => {
+        $(impl HeapSize for $ty {})*
+    };
+}
+
+no_heap!(
+    u8,
+    u32,
+    i32,
+    f32,
+    f64,
+    Vector3D,
+    Color4D,
+    Texel,
+    VertexWeight,
+    VectorKey,
+    QuatKey,
+    MeshKey
+);
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, T::heap_size)
+    }
+}
+
+impl HeapSize for Face {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
+impl HeapSize for Camera {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size()
+    }
+}
+
+impl HeapSize for Light {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size()
+    }
+}
+
+impl HeapSize for Bone {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size() + self.weights.heap_size()
+    }
+}
+
+impl HeapSize for AnimMesh {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size()
+            + self.vertices.heap_size()
+            + self.normals.heap_size()
+            + self.tangents.heap_size()
+            + self.bitangents.heap_size()
+            + self.colors.heap_size()
+            + self.texture_coords.heap_size()
+    }
+}
+
+impl HeapSize for Mesh {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size()
+            + self.vertices.heap_size()
+            + self.normals.heap_size()
+            + self.tangents.heap_size()
+            + self.bitangents.heap_size()
+            + self.texture_coords.heap_size()
+            + self.uv_components.heap_size()
+            + self.colors.heap_size()
+            + self.faces.heap_size()
+            + self.bones.heap_size()
+            + self.anim_meshes.heap_size()
+    }
+}
+
+impl HeapSize for PropertyTypeInfo {
+    fn heap_size(&self) -> usize {
+        match self {
+            PropertyTypeInfo::Buffer(data) => data.heap_size(),
+            PropertyTypeInfo::IntegerArray(data) => data.heap_size(),
+            PropertyTypeInfo::FloatArray(data) => data.heap_size(),
+            PropertyTypeInfo::String(data) => data.heap_size(),
+        }
+    }
+}
+
+impl HeapSize for MaterialProperty {
+    fn heap_size(&self) -> usize {
+        self.key.heap_size() + self.data.heap_size()
+    }
+}
+
+impl HeapSize for Material {
+    /// The textures themselves are counted separately, they may be shared.
+    fn heap_size(&self) -> usize {
+        // a hash map stores one control byte next to every entry
+        let entry = size_of::<(crate::material::TextureType, Rc<RefCell<Texture>>)>() + 1;
+        self.properties.heap_size() + self.textures.capacity() * entry
+    }
+}
+
+impl HeapSize for Texture {
+    fn heap_size(&self) -> usize {
+        let data = match &self.data {
+            DataContent::Texel(texels) => texels.heap_size(),
+            DataContent::Bytes(bytes) => bytes.heap_size(),
+        };
+        self.filename.heap_size() + self.ach_format_hint.heap_size() + data
+    }
+}
+
+impl HeapSize for MetaDataEntry {
+    fn heap_size(&self) -> usize {
+        match &self.0 {
+            Ok(MetadataType::String(value)) => value.heap_size(),
+            _ => 0,
+        }
+    }
+}
+
+impl HeapSize for MetaData {
+    fn heap_size(&self) -> usize {
+        self.keys.heap_size() + self.values.heap_size()
+    }
+}
+
+impl HeapSize for Node {
+    fn heap_size(&self) -> usize {
+        let children = self.children.borrow();
+        self.name.heap_size()
+            + self.meshes.heap_size()
+            + self.metadata.heap_size()
+            + children.capacity() * size_of::<Rc<Node>>()
+            + children
+                .iter()
+                .map(|child| rc_size::<Node>() + child.heap_size())
+                .sum::<usize>()
+    }
+}
+
+impl HeapSize for NodeAnim {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size()
+            + self.position_keys.heap_size()
+            + self.rotation_keys.heap_size()
+            + self.scaling_keys.heap_size()
+    }
+}
+
+impl HeapSize for MeshMorphKey {
+    fn heap_size(&self) -> usize {
+        self.values.heap_size() + self.weights.heap_size()
+    }
+}
+
+impl HeapSize for MeshMorphAnim {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size() + self.keys.heap_size()
+    }
+}
+
+impl HeapSize for MeshAnim {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size() + self.keys.heap_size()
+    }
+}
+
+impl HeapSize for Animation {
+    fn heap_size(&self) -> usize {
+        self.name.heap_size()
+            + self.channels.heap_size()
+            + self.morph_mesh_channels.heap_size()
+            + self.mesh_channels.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        face::Face,
+        memory::HeapSize,
+        mesh::Mesh,
+        scene::{PostProcess, Scene},
+        utils, Vector3D,
+    };
+    use std::mem::size_of;
+
+    #[test]
+    fn heap_sizes_follow_capacity() {
+        let mut faces = Vec::with_capacity(2);
+        faces.push(Face(vec![0, 1, 2]));
+        assert_eq!(2 * size_of::<Face>() + 3 * 4, faces.heap_size());
+
+        let mesh = Mesh {
+            name: "Cube".to_string(),
+            vertices: vec![Vector3D::default(); 8],
+            faces,
+            ..Mesh::default()
+        };
+        assert_eq!(
+            4 + 8 * size_of::<Vector3D>() + 2 * size_of::<Face>() + 3 * 4,
+            mesh.heap_size()
+        );
+    }
+
+    #[test]
+    fn memory_usage_for_cube() {
+        let current_directory_buf = utils::get_model("models/OBJ/cube.obj");
+
+        let (scene, report) = Scene::from_file_with_report(
+            current_directory_buf.as_str(),
+            vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ],
+        )
+        .unwrap();
+
+        let native = report.native_memory;
+        let usage = scene.memory_usage();
+        assert!(native.meshes > 0);
+        assert!(usage.meshes > 0);
+        assert!(usage.materials > 0);
+        assert_eq!(
+            usage.textures
+                + usage.materials
+                + usage.meshes
+                + usage.nodes
+                + usage.animations
+                + usage.cameras
+                + usage.lights,
+            usage.total
+        );
+    }
+}
@@ -34,11 +34,13 @@ pub enum PrimitiveType {
     Triangle = aiPrimitiveType_aiPrimitiveType_TRIANGLE as _,
 }
 
-impl From<&aiMesh> for Mesh {
-    fn from(mesh: &aiMesh) -> Self {
+impl TryFrom<&aiMesh> for Mesh {
+    type Error = RussimpError;
+
+    fn try_from(mesh: &aiMesh) -> Russult<Self> {
         let normals = utils::get_vec(mesh.mNormals, mesh.mNumVertices);
 
-        Self {
+        Ok(Self {
             normals,
             name: mesh.mName.into(),
             vertices: utils::get_vec(mesh.mVertices, mesh.mNumVertices),
@@ -47,24 +49,122 @@ impl From<&aiMesh> for Mesh {
             bitangents: utils::get_vec(mesh.mBitangents, mesh.mNumVertices),
             uv_components: mesh.mNumUVComponents.to_vec(),
             primitive_types: mesh.mPrimitiveTypes,
-            bones: utils::get_vec_from_raw(mesh.mBones, mesh.mNumBones),
+            bones: utils::get_vec_from_raw(mesh.mBones, mesh.mNumBones)?,
             material_index: mesh.mMaterialIndex,
             method: mesh.mMethod as u32,
-            anim_meshes: utils::get_vec_from_raw(mesh.mAnimMeshes, mesh.mNumAnimMeshes),
+            anim_meshes: utils::get_vec_from_raw(mesh.mAnimMeshes, mesh.mNumAnimMeshes)?,
             faces: utils::get_vec(mesh.mFaces, mesh.mNumFaces),
             colors: utils::get_vec_of_vecs_from_raw(mesh.mColors, mesh.mNumVertices),
             aabb: (&mesh.mAABB).into(),
-        }
+        })
     }
 }
 
-#[derive(Derivative)]
+#[derive(Derivative, num_enum::IntoPrimitive, num_enum::FromPrimitive, PartialEq, Clone, Copy)]
+#[derivative(Debug)]
+#[repr(u32)]
+pub enum MorphingMethod {
+    #[num_enum(default)]
+    Unknown = aiMorphingMethod_aiMorphingMethod_UNKNOWN as _,
+    VertexBlend = aiMorphingMethod_aiMorphingMethod_VERTEX_BLEND as _,
+    MorphNormalized = aiMorphingMethod_aiMorphingMethod_MORPH_NORMALIZED as _,
+    MorphRelative = aiMorphingMethod_aiMorphingMethod_MORPH_RELATIVE as _,
+}
+
+#[derive(Default, Derivative)]
 #[derivative(Debug)]
-pub struct AnimMesh(pub Vec<Vector3D>);
+pub struct AnimMesh {
+    pub name: String,
+    pub vertices: Vec<Vector3D>,
+    pub normals: Vec<Vector3D>,
+    pub tangents: Vec<Vector3D>,
+    pub bitangents: Vec<Vector3D>,
+    pub colors: Vec<Option<Vec<Color4D>>>,
+    pub texture_coords: Vec<Option<Vec<Vector3D>>>,
+    pub weight: f32,
+}
 
 impl From<&aiAnimMesh> for AnimMesh {
     fn from(mesh: &aiAnimMesh) -> Self {
-        Self(utils::get_vec(mesh.mBitangents, mesh.mNumVertices))
+        Self {
+            name: mesh.mName.into(),
+            vertices: utils::get_vec(mesh.mVertices, mesh.mNumVertices),
+            normals: utils::get_vec(mesh.mNormals, mesh.mNumVertices),
+            tangents: utils::get_vec(mesh.mTangents, mesh.mNumVertices),
+            bitangents: utils::get_vec(mesh.mBitangents, mesh.mNumVertices),
+            colors: utils::get_vec_of_vecs_from_raw(mesh.mColors, mesh.mNumVertices),
+            texture_coords: utils::get_vec_of_vecs_from_raw(mesh.mTextureCoords, mesh.mNumVertices),
+            weight: mesh.mWeight,
+        }
+    }
+}
+
+/// Vertex data of a [`Mesh`] after its morph targets have been applied.
+#[derive(Default, Derivative)]
+#[derivative(Debug)]
+pub struct MorphedMesh {
+    pub vertices: Vec<Vector3D>,
+    pub normals: Vec<Vector3D>,
+}
+
+impl Mesh {
+    /// Blends the [anim meshes](Mesh::anim_meshes) into the base mesh using
+    /// one weight per anim mesh, as returned by
+    /// [`Animation::sample_morph_weights`](crate::animation::Animation::sample_morph_weights).
+    ///
+    /// Missing weights count as zero. Anim meshes are expected to hold absolute
+    /// positions and normals, which is how assimp stores them. With
+    /// [`MorphingMethod::MorphNormalized`] the weights are scaled down so they
+    /// never add up to more than one, every other method adds the weighted
+    /// differences to the base mesh as they are.
+    pub fn apply_morph_weights(&self, weights: &[f32]) -> MorphedMesh {
+        let mut weights: Vec<f32> = (0..self.anim_meshes.len())
+            .map(|index| weights.get(index).copied().unwrap_or(0.0))
+            .collect();
+
+        if MorphingMethod::from(self.method) == MorphingMethod::MorphNormalized {
+            let total: f32 = weights.iter().sum();
+            if total > 1.0 {
+                weights.iter_mut().for_each(|weight| *weight /= total);
+            }
+        }
+
+        let vertices = Self::blend_targets(
+            &self.vertices,
+            self.anim_meshes.iter().map(|anim_mesh| &anim_mesh.vertices),
+            &weights,
+        );
+        let normals = Self::blend_targets(
+            &self.normals,
+            self.anim_meshes.iter().map(|anim_mesh| &anim_mesh.normals),
+            &weights,
+        )
+        .iter()
+        .map(Vector3D::normalize)
+        .collect();
+
+        MorphedMesh { vertices, normals }
+    }
+
+    fn blend_targets<'a>(
+        base: &[Vector3D],
+        targets: impl Iterator<Item = &'a Vec<Vector3D>>,
+        weights: &[f32],
+    ) -> Vec<Vector3D> {
+        let mut result = base.to_vec();
+
+        for (target, &weight) in targets.zip(weights) {
+            // anim meshes are allowed to leave out components they do not morph
+            if weight == 0.0 || target.len() != base.len() {
+                continue;
+            }
+
+            for ((blended, &original), &morphed) in result.iter_mut().zip(base).zip(target) {
+                *blended = *blended + (morphed - original) * weight;
+            }
+        }
+
+        result
     }
 }
 
@@ -102,7 +202,63 @@ impl BitAnd<u32> for PrimitiveType {
 
 #[cfg(test)]
 mod test {
-    use crate::{mesh::PrimitiveType, utils};
+    use crate::{
+        mesh::{AnimMesh, Mesh, MorphingMethod, PrimitiveType},
+        utils, Vector3D,
+    };
+
+    fn morph_target_mesh(method: MorphingMethod) -> Mesh {
+        Mesh {
+            vertices: vec![Vector3D::new(0.0, 0.0, 0.0), Vector3D::new(1.0, 0.0, 0.0)],
+            normals: vec![Vector3D::new(0.0, 0.0, 1.0), Vector3D::new(0.0, 0.0, 1.0)],
+            method: method.into(),
+            anim_meshes: vec![
+                AnimMesh {
+                    vertices: vec![Vector3D::new(0.0, 2.0, 0.0), Vector3D::new(1.0, 2.0, 0.0)],
+                    normals: vec![Vector3D::new(0.0, 1.0, 0.0), Vector3D::new(0.0, 1.0, 0.0)],
+                    ..Default::default()
+                },
+                AnimMesh {
+                    vertices: vec![Vector3D::new(4.0, 0.0, 0.0), Vector3D::new(5.0, 0.0, 0.0)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn apply_relative_morph_weights() {
+        let mesh = morph_target_mesh(MorphingMethod::MorphRelative);
+
+        let morphed = mesh.apply_morph_weights(&[0.5, 1.0]);
+
+        assert_eq!(Vector3D::new(4.0, 1.0, 0.0), morphed.vertices[0]);
+        assert_eq!(Vector3D::new(5.0, 1.0, 0.0), morphed.vertices[1]);
+
+        let expected_normal = Vector3D::new(0.0, 0.5, 0.5).normalize();
+        assert_eq!(expected_normal, morphed.normals[0]);
+    }
+
+    #[test]
+    fn apply_normalized_morph_weights() {
+        let mesh = morph_target_mesh(MorphingMethod::MorphNormalized);
+
+        let morphed = mesh.apply_morph_weights(&[1.0, 1.0]);
+
+        assert_eq!(Vector3D::new(2.0, 1.0, 0.0), morphed.vertices[0]);
+        assert_eq!(Vector3D::new(3.0, 1.0, 0.0), morphed.vertices[1]);
+    }
+
+    #[test]
+    fn missing_morph_weights_keep_base_mesh() {
+        let mesh = morph_target_mesh(MorphingMethod::MorphRelative);
+
+        let morphed = mesh.apply_morph_weights(&[]);
+
+        assert_eq!(mesh.vertices, morphed.vertices);
+        assert_eq!(mesh.normals, morphed.normals);
+    }
 
     #[test]
     fn mesh_available() {
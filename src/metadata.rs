@@ -42,9 +42,7 @@ impl<'a> MetaDataEntryCast for MetaDataEntryULong<'a> {
             return Ok(MetadataType::ULong(*result));
         }
 
-        Err(RussimpError::MetadataError(
-            "Cant convert to ulong".to_string(),
-        ))
+        Err(RussimpError::Metadata("Cant convert to ulong".to_string()))
     }
 }
 
@@ -60,7 +58,7 @@ impl<'a> MetaDataEntryCast for MetaDataEntryInteger<'a> {
             return Ok(MetadataType::Int(*result));
         }
 
-        Err(RussimpError::MetadataError(
+        Err(RussimpError::Metadata(
             "Cant convert to integer".to_string(),
         ))
     }
@@ -78,9 +76,7 @@ impl<'a> MetaDataEntryCast for MetaDataEntryBool<'a> {
             return Ok(MetadataType::Bool(*result));
         }
 
-        Err(RussimpError::MetadataError(
-            "Cant convert to bool".to_string(),
-        ))
+        Err(RussimpError::Metadata("Cant convert to bool".to_string()))
     }
 }
 
@@ -96,9 +92,7 @@ impl<'a> MetaDataEntryCast for MetaDataEntryDouble<'a> {
             return Ok(MetadataType::Double(*result));
         }
 
-        Err(RussimpError::MetadataError(
-            "Cant convert to double".to_string(),
-        ))
+        Err(RussimpError::Metadata("Cant convert to double".to_string()))
     }
 }
 
@@ -114,9 +108,7 @@ impl<'a> MetaDataEntryCast for MetaDataEntryFloat<'a> {
             return Ok(MetadataType::Float(*result));
         }
 
-        Err(RussimpError::MetadataError(
-            "Cant convert to float".to_string(),
-        ))
+        Err(RussimpError::Metadata("Cant convert to float".to_string()))
     }
 }
 
@@ -131,9 +123,7 @@ impl<'a> MetaDataEntryCast for MetaDataEntryString<'a> {
         if let Some(result) = unsafe { raw.as_ref() } {
             Ok(MetadataType::String(result.into()))
         } else {
-            Err(RussimpError::MetadataError(
-                "Cant convert to string".to_string(),
-            ))
+            Err(RussimpError::Metadata("Cant convert to string".to_string()))
         }
     }
 }
@@ -153,7 +143,7 @@ impl<'a> MetaDataEntryCast for MetaDataVector3d<'a> {
             return Ok(MetadataType::Vector3d(content.into()));
         }
 
-        Err(RussimpError::MetadataError("data is null".to_string()))
+        Err(RussimpError::Metadata("data is null".to_string()))
     }
 }
 
@@ -210,7 +200,7 @@ impl MetaDataEntry {
             }
         }
 
-        Err(RussimpError::MetadataError(
+        Err(RussimpError::Metadata(
             "could not find caster for metadata type".to_string(),
         ))
     }
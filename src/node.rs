@@ -18,21 +18,21 @@ pub struct Node {
 }
 
 impl Node {
-    pub(crate) fn new(node: &aiNode) -> Rc<Node> {
+    pub(crate) fn new(node: &aiNode) -> Russult<Rc<Node>> {
         Self::allocate(node, None)
     }
 
-    fn allocate(node: &aiNode, parent: Option<&Rc<Node>>) -> Rc<Node> {
+    fn allocate(node: &aiNode, parent: Option<&Rc<Node>>) -> Russult<Rc<Node>> {
         // current simple node
         let res_node = Rc::new(Self::create_simple_node(node, parent));
 
         *res_node.children.borrow_mut() =
-            utils::get_base_type_vec_from_raw(node.mChildren, node.mNumChildren)
+            utils::get_base_type_vec_from_raw(node.mChildren, node.mNumChildren)?
                 .into_iter()
                 .map(|child| Self::allocate(child, Some(&res_node)))
-                .collect::<Vec<_>>();
+                .collect::<Russult<Vec<_>>>()?;
 
-        res_node
+        Ok(res_node)
     }
 
     fn create_simple_node(node: &aiNode, parent: Option<&Rc<Node>>) -> Node {
@@ -45,6 +45,36 @@ impl Node {
             parent: parent.map(Rc::downgrade).unwrap_or_else(Weak::new),
         }
     }
+
+    /// Searches the descendants of this node for a node called `name`.
+    pub fn find(&self, name: &str) -> Option<Rc<Node>> {
+        self.children.borrow().iter().find_map(|child| {
+            if child.name == name {
+                Some(child.clone())
+            } else {
+                child.find(name)
+            }
+        })
+    }
+
+    /// Global transformation of the node called `name`, searched below and
+    /// including this node.
+    pub(crate) fn global_transformation_of(&self, name: &str) -> Option<Matrix4x4> {
+        if self.name == name {
+            return Some(self.global_transformation());
+        }
+
+        self.find(name).map(|node| node.global_transformation())
+    }
+
+    /// Transformation from this node's local space to the space of the root
+    /// node, i.e. the product of all transformations up the hierarchy.
+    pub fn global_transformation(&self) -> Matrix4x4 {
+        match self.parent.upgrade() {
+            Some(parent) => parent.global_transformation() * self.transformation,
+            None => self.transformation,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,406 @@
+//! The `progress` module reports how far an import got and allows cancelling
+//! it, see [`Importer::progress`](crate::importer::Importer::progress).
+//!
+//! Assimp's C API doesn't expose its progress handler, so russimp derives the
+//! progress from the reads of the imported file and from the log messages
+//! assimp emits when post processing starts. Assimp only announces the single
+//! post processing steps as debug messages, those are reported too while
+//! [`logging::set_verbose`] is enabled, which affects every import of the
+//! process. Reading stops as soon as the handler cancels the import. Post
+//! processing can't be interrupted through the C API, a cancellation during
+//! post processing takes effect once it finished and discards the scene.
+use crate::{
+    fs::{FileOperations, FileSystem},
+    logging::{self, LogMessage, Severity},
+    RussimpError, Russult,
+};
+use std::{
+    any::Any,
+    cell::RefCell,
+    io::{self, SeekFrom},
+    panic::{self, AssertUnwindSafe},
+};
+
+/// Receives the progress of an import. Every method returns whether the
+/// import should go on, returning `false` cancels it with
+/// [`RussimpError::Cancelled`].
+///
+/// Closures taking the overall progress work as handlers:
+///
+/// ```no_run
+/// use russimp::{importer::Importer, scene::PostProcess};
+///
+/// let mut handler = |progress: f32| {
+///     println!("{:.0}%", progress * 100.0);
+///     true
+/// };
+/// let scene = Importer::new()
+///     .post_process(vec![PostProcess::Triangulate])
+///     .progress(&mut handler)
+///     .read_file("models/FBX/y_bot_run.fbx");
+/// ```
+pub trait ProgressHandler {
+    /// Overall progress between 0 and 1.
+    fn update(&mut self, percentage: f32) -> bool;
+
+    /// `current` of `total` bytes of the imported file were read. Reading
+    /// takes up the first half of the overall progress.
+    fn update_file_read(&mut self, current: usize, total: usize) -> bool {
+        let fraction = if total > 0 {
+            current as f32 / total as f32
+        } else {
+            1.0
+        };
+
+        self.update(fraction.min(1.0) * 0.5)
+    }
+
+    /// `current` of `total` post processing steps finished. Post processing
+    /// takes up the second half of the overall progress.
+    fn update_post_process(&mut self, current: usize, total: usize) -> bool {
+        let fraction = if total > 0 {
+            current as f32 / total as f32
+        } else {
+            1.0
+        };
+
+        self.update(fraction.min(1.0) * 0.5 + 0.5)
+    }
+}
+
+impl<F: FnMut(f32) -> bool> ProgressHandler for F {
+    fn update(&mut self, percentage: f32) -> bool {
+        self(percentage)
+    }
+}
+
+struct Tracker {
+    handler: *mut dyn ProgressHandler,
+    cancelled: bool,
+    panic: Option<Box<dyn Any + Send>>,
+    steps: usize,
+    step: usize,
+}
+
+impl Tracker {
+    fn notify(&mut self, update: impl FnOnce(&mut dyn ProgressHandler) -> bool) {
+        if self.cancelled {
+            return;
+        }
+
+        let handler = self.handler;
+        // the handler runs inside of assimp's callbacks, which must not unwind
+        match panic::catch_unwind(AssertUnwindSafe(|| update(unsafe { &mut *handler }))) {
+            Ok(go_on) => self.cancelled = !go_on,
+            Err(payload) => {
+                self.cancelled = true;
+                self.panic = Some(payload);
+            }
+        }
+    }
+
+    fn on_log(&mut self, message: &LogMessage) {
+        let steps = self.steps;
+        if message.severity == Severity::Info
+            && message.message == "Entering post processing pipeline"
+        {
+            self.notify(|handler| handler.update_post_process(0, steps));
+        }
+
+        // with verbose logging post processing steps announce themselves as
+        // e.g. "TriangulateProcess begin", the first one started right away
+        if message.severity == Severity::Debug && message.message.ends_with(" begin") {
+            let step = self.step;
+            if step > 0 {
+                self.notify(|handler| handler.update_post_process(step, steps));
+            }
+            self.step = (self.step + 1).min(self.steps);
+        }
+    }
+}
+
+thread_local! {
+    static TRACKER: RefCell<Option<Tracker>> = const { RefCell::new(None) };
+}
+
+fn with_tracker(f: impl FnOnce(&mut Tracker)) {
+    TRACKER.with(|tracker| {
+        if let Some(tracker) = tracker.borrow_mut().as_mut() {
+            f(tracker);
+        }
+    });
+}
+
+fn is_cancelled() -> bool {
+    TRACKER.with(|tracker| {
+        tracker
+            .borrow()
+            .as_ref()
+            .is_some_and(|tracker| tracker.cancelled)
+    })
+}
+
+/// Runs `import` while reporting its progress to `handler`. `steps` is the
+/// number of post processing steps the import will run.
+pub(crate) fn track<R>(
+    handler: &mut dyn ProgressHandler,
+    steps: usize,
+    import: impl FnOnce() -> Russult<R>,
+) -> Russult<R> {
+    // SAFETY: the tracker is removed again before `handler` goes out of scope
+    let handler: *mut dyn ProgressHandler = unsafe { std::mem::transmute(handler) };
+    let previous = TRACKER.with(|tracker| {
+        tracker.borrow_mut().replace(Tracker {
+            handler,
+            cancelled: false,
+            panic: None,
+            steps,
+            step: 0,
+        })
+    });
+
+    struct TrackerGuard(Option<Tracker>);
+
+    impl Drop for TrackerGuard {
+        fn drop(&mut self) {
+            TRACKER.with(|tracker| *tracker.borrow_mut() = self.0.take());
+        }
+    }
+
+    let guard = TrackerGuard(previous);
+    let result = logging::observe(
+        |message| with_tracker(|tracker| tracker.on_log(message)),
+        import,
+    );
+
+    if result.is_ok() {
+        with_tracker(|tracker| tracker.notify(|handler| handler.update_post_process(steps, steps)));
+    }
+
+    let tracker = TRACKER.with(|tracker| tracker.borrow_mut().take());
+    drop(guard);
+
+    match tracker {
+        Some(Tracker {
+            panic: Some(payload),
+            ..
+        }) => panic::resume_unwind(payload),
+        Some(Tracker {
+            cancelled: true, ..
+        }) => Err(RussimpError::Cancelled),
+        _ => result,
+    }
+}
+
+/// Reports the reads of the file at `path` to the active tracker and fails
+/// all reads once the import was cancelled.
+pub(crate) struct ProgressFileSystem<'a, T: FileSystem + ?Sized> {
+    inner: &'a T,
+    path: &'a str,
+}
+
+impl<'a, T: FileSystem + ?Sized> ProgressFileSystem<'a, T> {
+    pub(crate) fn new(inner: &'a T, path: &'a str) -> Self {
+        Self { inner, path }
+    }
+}
+
+impl<'a, T: FileSystem + ?Sized> FileSystem for ProgressFileSystem<'a, T> {
+    fn open(&self, file_path: &str, mode: &str) -> io::Result<Box<dyn FileOperations>> {
+        if is_cancelled() {
+            return Err(cancelled());
+        }
+
+        let file = self.inner.open(file_path, mode)?;
+        Ok(Box::new(ProgressFile {
+            inner: file,
+            tracked: file_path == self.path,
+        }))
+    }
+}
+
+fn cancelled() -> io::Error {
+    io::Error::other("the import was cancelled")
+}
+
+struct ProgressFile {
+    inner: Box<dyn FileOperations>,
+    /// Whether this is the imported file, other files like textures or
+    /// material libraries don't count towards the progress.
+    tracked: bool,
+}
+
+impl FileOperations for ProgressFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if is_cancelled() {
+            return Err(cancelled());
+        }
+
+        let read = self.inner.read(buf)?;
+        if self.tracked {
+            let (current, total) = (self.inner.tell()?, self.inner.size()?);
+            with_tracker(|tracker| {
+                tracker.notify(|handler| handler.update_file_read(current, total))
+            });
+        }
+
+        Ok(read)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn tell(&mut self) -> io::Result<usize> {
+        self.inner.tell()
+    }
+
+    fn size(&mut self) -> io::Result<usize> {
+        self.inner.size()
+    }
+
+    fn seek(&mut self, seek_from: SeekFrom) -> io::Result<()> {
+        if is_cancelled() {
+            return Err(cancelled());
+        }
+
+        self.inner.seek(seek_from)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    fn close(&mut self) {
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        fs::{FileSystem, StdFileSystem},
+        logging::{LogMessage, Severity},
+        progress::{self, ProgressFileSystem},
+        utils, RussimpError,
+    };
+    use std::io;
+
+    fn read_file(path: &str) -> io::Result<usize> {
+        let file_system = StdFileSystem::default();
+        let file_system = ProgressFileSystem::new(&file_system, path);
+        let mut file = file_system.open(path, "rb")?;
+
+        let mut total = 0;
+        let mut buf = [0; 64];
+        loop {
+            match file.read(&mut buf)? {
+                0 => return Ok(total),
+                read => total += read,
+            }
+        }
+    }
+
+    #[test]
+    fn file_reads_are_reported() {
+        let path = utils::get_model("models/OBJ/cube.obj");
+        let mut updates = vec![];
+
+        let read = progress::track(
+            &mut |progress: f32| {
+                updates.push(progress);
+                true
+            },
+            2,
+            || Ok(read_file(&path).unwrap()),
+        )
+        .unwrap();
+
+        assert!(read > 64);
+        assert!(updates.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(Some(&0.5), updates.iter().rev().nth(1));
+        assert_eq!(Some(&1.0), updates.last());
+    }
+
+    #[test]
+    fn cancelling_stops_reading() {
+        let path = utils::get_model("models/OBJ/cube.obj");
+        let mut updates = 0;
+
+        let result = progress::track(
+            &mut |_| {
+                updates += 1;
+                false
+            },
+            2,
+            || read_file(&path).map_err(RussimpError::Io),
+        );
+
+        assert!(matches!(result, Err(RussimpError::Cancelled)));
+        assert_eq!(1, updates);
+    }
+
+    #[test]
+    fn post_processing_is_reported_from_log_messages() {
+        let log = |severity, message: &str| {
+            let message = LogMessage {
+                severity,
+                message: message.to_string(),
+            };
+            progress::with_tracker(|tracker| tracker.on_log(&message));
+        };
+        let mut updates = vec![];
+
+        progress::track(
+            &mut |progress: f32| {
+                updates.push(progress);
+                true
+            },
+            2,
+            || {
+                log(Severity::Info, "Found a matching importer");
+                log(Severity::Info, "Entering post processing pipeline");
+                log(Severity::Debug, "TriangulateProcess begin");
+                log(Severity::Debug, "SortByPTypeProcess begin");
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(vec![0.5, 0.75, 1.0], updates);
+    }
+
+    #[test]
+    fn import_with_progress() {
+        use crate::{importer::Importer, scene::PostProcess};
+
+        let current_directory_buf = utils::get_model("models/OBJ/cube.obj");
+        let mut updates = vec![];
+        let mut handler = |progress: f32| {
+            updates.push(progress);
+            true
+        };
+
+        let scene = Importer::new()
+            .post_process(vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ])
+            .progress(&mut handler)
+            .read_file(current_directory_buf.as_str())
+            .unwrap();
+
+        assert_eq!(2, scene.materials.len());
+        assert!(updates.iter().any(|&progress| progress < 0.5));
+        assert!(updates.iter().any(|&progress| progress > 0.5));
+        assert_eq!(Some(&1.0), updates.last());
+
+        let cancelled = Importer::new()
+            .post_process(vec![PostProcess::Triangulate])
+            .progress(&mut |_| false)
+            .read_file(current_directory_buf.as_str());
+        assert!(matches!(cancelled, Err(RussimpError::Cancelled)));
+    }
+}
@@ -1,4 +1,6 @@
-use std::ffi::CStr;
+use std::ffi::CString;
+
+use crate::config::ImportConfig;
 
 use russimp_sys::{
     aiCreatePropertyStore, aiMatrix4x4, aiPropertyStore, aiReleasePropertyStore,
@@ -13,6 +15,16 @@ pub enum Property {
     Matrix([[f32; 4]; 4]),
 }
 
+/// Property names are C strings. The NUL terminator of the `AI_CONFIG_*`
+/// constants is optional, anything after the first NUL is ignored.
+fn property_name(name: &[u8]) -> CString {
+    let end = name
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(name.len());
+    CString::new(&name[..end]).unwrap_or_default()
+}
+
 pub struct PropertyStore {
     ptr: *mut aiPropertyStore,
 }
@@ -33,29 +45,31 @@ impl Default for PropertyStore {
 
 impl PropertyStore {
     pub fn set_integer(&mut self, name: &[u8], value: i32) {
-        let c_name = CStr::from_bytes_until_nul(name).unwrap();
+        let c_name = property_name(name);
         unsafe { aiSetImportPropertyInteger(self.ptr, c_name.as_ptr(), value) };
     }
 
     pub fn set_float(&mut self, name: &[u8], value: f32) {
-        let c_name = CStr::from_bytes_until_nul(name).unwrap();
+        let c_name = property_name(name);
         unsafe { aiSetImportPropertyFloat(self.ptr, c_name.as_ptr(), value) };
     }
 
     pub fn set_string(&mut self, name: &[u8], value: &str) {
-        let c_name = CStr::from_bytes_until_nul(name).unwrap();
+        let c_name = property_name(name);
         let bytes: &[::std::os::raw::c_char] = unsafe { std::mem::transmute(value.as_bytes()) };
         let mut string = aiString {
-            length: bytes.len() as u32,
+            length: 0,
             data: [0; 1024],
         };
-        let n = std::cmp::min(string.data.len(), bytes.len());
+        // keep the last byte for the NUL terminator
+        let n = std::cmp::min(string.data.len() - 1, bytes.len());
+        string.length = n as u32;
         string.data[0..n].copy_from_slice(&bytes[0..n]);
         unsafe { aiSetImportPropertyString(self.ptr, c_name.as_ptr(), &string as *const aiString) };
     }
 
     pub fn set_matrix(&mut self, name: &[u8], value: [[f32; 4]; 4]) {
-        let c_name = CStr::from_bytes_until_nul(name).unwrap();
+        let c_name = property_name(name);
         // NOTE: Assuming column-major matrix
         let matrix = aiMatrix4x4 {
             a1: value[0][0],
@@ -80,6 +94,11 @@ impl PropertyStore {
         };
     }
 
+    /// Sets a typed setting, see [`ImportConfig`].
+    pub fn set(&mut self, config: ImportConfig) {
+        config.apply(self);
+    }
+
     pub(crate) fn as_ptr(&self) -> *mut aiPropertyStore {
         self.ptr
     }
@@ -99,3 +118,36 @@ impl<T: Iterator<Item = (&'static [u8], Property)>> From<T> for PropertyStore {
         props
     }
 }
+
+impl FromIterator<ImportConfig> for PropertyStore {
+    fn from_iter<T: IntoIterator<Item = ImportConfig>>(iter: T) -> Self {
+        let mut props = Self::default();
+        props.extend(iter);
+        props
+    }
+}
+
+impl Extend<ImportConfig> for PropertyStore {
+    fn extend<T: IntoIterator<Item = ImportConfig>>(&mut self, iter: T) {
+        for config in iter {
+            self.set(config);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::property::property_name;
+
+    #[test]
+    fn names_without_nul() {
+        assert_eq!(
+            b"PP_SLM_VERTEX_LIMIT",
+            property_name(b"PP_SLM_VERTEX_LIMIT").as_bytes()
+        );
+        assert_eq!(
+            b"PP_SLM_VERTEX_LIMIT",
+            property_name(b"PP_SLM_VERTEX_LIMIT\0\0").as_bytes()
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! The `report` module collects diagnostics of successful imports, see
+//! [`Scene::from_file_with_report`](crate::scene::Scene::from_file_with_report).
+use crate::{
+    logging::{self, LogMessage, Severity},
+    memory::MemoryInfo,
+    property::PropertyStore,
+    scene::{RawScene, Scene, SceneFlags},
+    Russult,
+};
+use derivative::Derivative;
+use std::time::Duration;
+
+/// Enables assimp's profiler, which logs the time spent in every import
+/// stage.
+const GLOB_MEASURE_TIME: &[u8] = b"GLOB_MEASURE_TIME\0";
+
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
+pub struct StepTiming {
+    /// Either an import stage (`import`, `preprocess`, `postprocess`,
+    /// `total`) or the post processing step that ran, e.g.
+    /// `TriangulateProcess`.
+    pub name: String,
+    pub duration: Duration,
+}
+
+#[derive(Clone, Default, Derivative)]
+#[derivative(Debug)]
+pub struct ImportReport {
+    /// Name of the importer that read the file.
+    pub importer: Option<String>,
+    pub warnings: Vec<String>,
+    /// Errors that assimp recovered from, e.g. textures that failed to load.
+    pub errors: Vec<String>,
+    /// Timings in the order the stages finished.
+    pub timings: Vec<StepTiming>,
+    pub flags: SceneFlags,
+    /// The memory assimp used for the scene before it was converted, see
+    /// [`Scene::memory_usage`] for the converted scene.
+    pub native_memory: MemoryInfo,
+}
+
+impl ImportReport {
+    /// Whether assimp logged any warnings or errors, or the validation step
+    /// found problems.
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+            || !self.errors.is_empty()
+            || self.flags.contains(SceneFlags::VALIDATION_WARNING)
+    }
+
+    /// Time spent in the stage or step called `name`.
+    pub fn timing(&self, name: &str) -> Option<Duration> {
+        self.timings
+            .iter()
+            .find(|timing| timing.name == name)
+            .map(|timing| timing.duration)
+    }
+}
+
+/// Enables the profiler in `props` and verbose logging, runs `import` with
+/// `props` and builds the report from the messages it logged and the
+/// imported scene.
+pub(crate) fn collect(
+    mut props: PropertyStore,
+    import: impl FnOnce(&PropertyStore) -> Russult<RawScene>,
+) -> Russult<(Scene, ImportReport)> {
+    props.set_integer(GLOB_MEASURE_TIME, 1);
+    let mut collector = ReportCollector::default();
+    let raw_scene =
+        logging::verbose(|| logging::observe(|message| collector.add(message), || import(&props)))?;
+
+    let native_memory = raw_scene.native_memory();
+    let scene = raw_scene.convert()?;
+    let report = collector.finish(scene.flags, native_memory);
+    Ok((scene, report))
+}
+
+/// Builds an [`ImportReport`] from the log messages of an import.
+#[derive(Default)]
+pub(crate) struct ReportCollector {
+    report: ImportReport,
+    /// The post processing step that logged its start last.
+    step: Option<String>,
+}
+
+impl ReportCollector {
+    pub(crate) fn add(&mut self, message: &LogMessage) {
+        let text = message.message.as_str();
+
+        match message.severity {
+            Severity::Warn => self.report.warnings.push(text.to_string()),
+            Severity::Error => self.report.errors.push(text.to_string()),
+            Severity::Info => {
+                if let Some(importer) =
+                    text.strip_prefix("Found a matching importer for this file format: ")
+                {
+                    self.report.importer = Some(importer.trim_end_matches('.').to_string());
+                }
+            }
+            Severity::Debug => {
+                // steps announce themselves as e.g. "TriangulateProcess begin"
+                if let Some(step) = text.strip_suffix(" begin") {
+                    self.step = Some(step.to_string());
+                } else if let Some(timing) = self.parse_timing(text) {
+                    self.report.timings.push(timing);
+                }
+            }
+        }
+    }
+
+    /// Parses profiler lines like "END   `total`, dt= 0.0421 s".
+    fn parse_timing(&mut self, text: &str) -> Option<StepTiming> {
+        let rest = text.strip_prefix("END")?.trim_start().strip_prefix('`')?;
+        let (region, seconds) = rest.split_once("`, dt= ")?;
+        let seconds: f64 = seconds.strip_suffix(" s")?.trim().parse().ok()?;
+
+        // the profiler measures every post processing step as "postprocess"
+        let name = match region {
+            "postprocess" => self.step.take().unwrap_or_else(|| region.to_string()),
+            region => region.to_string(),
+        };
+
+        Some(StepTiming {
+            name,
+            duration: Duration::try_from_secs_f64(seconds).unwrap_or_default(),
+        })
+    }
+
+    pub(crate) fn finish(self, flags: SceneFlags, native_memory: MemoryInfo) -> ImportReport {
+        ImportReport {
+            flags,
+            native_memory,
+            ..self.report
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        logging::{LogMessage, Severity},
+        memory::MemoryInfo,
+        report::ReportCollector,
+        scene::SceneFlags,
+        utils,
+    };
+    use std::time::Duration;
+
+    fn message(severity: Severity, message: &str) -> LogMessage {
+        LogMessage {
+            severity,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn collect_report_from_log() {
+        let mut collector = ReportCollector::default();
+        for log in [
+            message(
+                Severity::Info,
+                "Found a matching importer for this file format: Wavefront Object Importer.",
+            ),
+            message(
+                Severity::Warn,
+                "OBJ: Unable to locate material file box.mtl",
+            ),
+            message(Severity::Debug, "END   `import`, dt= 0.5 s"),
+            message(Severity::Debug, "TriangulateProcess begin"),
+            message(Severity::Debug, "END   `postprocess`, dt= 0.25 s"),
+            message(Severity::Debug, "END   `total`, dt= not a number s"),
+        ] {
+            collector.add(&log);
+        }
+
+        let report = collector.finish(SceneFlags::VALIDATED, MemoryInfo::default());
+
+        assert_eq!(
+            Some("Wavefront Object Importer"),
+            report.importer.as_deref()
+        );
+        assert_eq!(1, report.warnings.len());
+        assert!(report.errors.is_empty());
+        assert!(report.has_warnings());
+        assert_eq!(2, report.timings.len());
+        assert_eq!(Some(Duration::from_millis(500)), report.timing("import"));
+        assert_eq!(
+            Some(Duration::from_millis(250)),
+            report.timing("TriangulateProcess")
+        );
+        assert_eq!(None, report.timing("total"));
+    }
+
+    #[test]
+    fn import_with_report() {
+        use crate::scene::{PostProcess, Scene};
+
+        let current_directory_buf = utils::get_model("models/BLEND/box.blend");
+
+        let (scene, report) = Scene::from_file_with_report(
+            current_directory_buf.as_str(),
+            vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(scene.flags, report.flags);
+        assert!(report.native_memory.meshes > 0);
+        assert!(report.flags.contains(SceneFlags::NON_VERBOSE_FORMAT));
+        assert!(report
+            .importer
+            .as_deref()
+            .unwrap_or_default()
+            .starts_with("Blender 3D Importer"));
+        assert!(report.timing("total").is_some());
+        assert!(report.timing("TriangulateProcess").is_some());
+    }
+}
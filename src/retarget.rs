@@ -0,0 +1,401 @@
+//! The `retarget` module transfers node animations between skeletons with
+//! different rest poses and bone names.
+//!
+//! Every key is turned into an offset from the source rest pose of its node,
+//! which is then applied on top of the rest pose of the mapped target node.
+use crate::{
+    animation::{Animation, NodeAnim, QuatKey, Quaternion, VectorKey},
+    node::Node,
+    scene::Scene,
+    Matrix4x4, Vector3D,
+};
+use derivative::Derivative;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Derivative)]
+#[derivative(Debug)]
+pub struct RestTransform {
+    pub scaling: Vector3D,
+    pub rotation: Quaternion,
+    pub position: Vector3D,
+}
+
+impl Default for RestTransform {
+    fn default() -> Self {
+        Self {
+            scaling: Vector3D::new(1.0, 1.0, 1.0),
+            rotation: Quaternion::identity(),
+            position: Vector3D::default(),
+        }
+    }
+}
+
+impl From<&Matrix4x4> for RestTransform {
+    fn from(matrix: &Matrix4x4) -> Self {
+        let (scaling, rotation, position) = matrix.decompose();
+
+        Self {
+            scaling,
+            rotation,
+            position,
+        }
+    }
+}
+
+/// Local rest transformation of every node of a skeleton, keyed by node name.
+#[derive(Default, Derivative)]
+#[derivative(Debug)]
+pub struct RestPose(pub HashMap<String, RestTransform>);
+
+impl RestPose {
+    /// Uses the [transformation](Node::transformation) of every node below
+    /// and including `root` as its rest transformation.
+    pub fn from_node(root: &Node) -> Self {
+        let mut pose = HashMap::new();
+        visit(root, &Matrix4x4::identity(), &mut |node, _| {
+            pose.entry(node.name.clone())
+                .or_insert_with(|| (&node.transformation).into());
+        });
+
+        Self(pose)
+    }
+
+    /// Like [`RestPose::from_node`] for the scene's root node, but nodes that
+    /// are used as bones take their rest transformation from the bind pose
+    /// stored in the [bone offset matrices](crate::bone::Bone::offset_matrix),
+    /// which can differ from the pose the node hierarchy was exported in.
+    pub fn from_scene(scene: &Scene) -> Self {
+        let root = match &scene.root {
+            Some(root) => root,
+            None => return Self::default(),
+        };
+
+        let mut globals = HashMap::new();
+        let mut parents = HashMap::new();
+        let mut mesh_globals = HashMap::new();
+        visit(root, &Matrix4x4::identity(), &mut |node, global| {
+            globals.entry(node.name.clone()).or_insert(*global);
+            if let Some(parent) = node.parent.upgrade() {
+                parents
+                    .entry(node.name.clone())
+                    .or_insert(parent.name.clone());
+            }
+            for &mesh in &node.meshes {
+                mesh_globals.entry(mesh as usize).or_insert(*global);
+            }
+        });
+
+        // bone offset matrices go from mesh space to bone space, so their
+        // inverse placed in the mesh node's space is the global bind pose
+        let mut binds = HashMap::new();
+        for (index, mesh) in scene.meshes.iter().enumerate() {
+            let mesh_global = mesh_globals
+                .get(&index)
+                .copied()
+                .unwrap_or_else(Matrix4x4::identity);
+
+            for bone in &mesh.bones {
+                if let Some(inverse_offset) = bone.offset_matrix.inverse() {
+                    binds
+                        .entry(bone.name.clone())
+                        .or_insert(mesh_global * inverse_offset);
+                }
+            }
+        }
+
+        let mut pose = Self::from_node(root);
+        for (name, bind) in &binds {
+            let parent_bind = parents
+                .get(name)
+                .and_then(|parent| binds.get(parent).or_else(|| globals.get(parent)))
+                .copied()
+                .unwrap_or_else(Matrix4x4::identity);
+
+            if let Some(inverse_parent) = parent_bind.inverse() {
+                pose.0
+                    .insert(name.clone(), (&(inverse_parent * *bind)).into());
+            }
+        }
+
+        pose
+    }
+}
+
+fn visit(node: &Node, parent_global: &Matrix4x4, f: &mut impl FnMut(&Node, &Matrix4x4)) {
+    let global = *parent_global * node.transformation;
+    f(node, &global);
+
+    for child in node.children.borrow().iter() {
+        visit(child, &global, f);
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct Retargeter {
+    source: RestPose,
+    target: RestPose,
+    bone_map: HashMap<String, String>,
+}
+
+impl Retargeter {
+    /// `bone_map` maps source node names to target node names. Channels of
+    /// nodes missing from the map are kept if the target skeleton has a node
+    /// with the same name, and dropped otherwise.
+    pub fn new(source: RestPose, target: RestPose, bone_map: HashMap<String, String>) -> Self {
+        Self {
+            source,
+            target,
+            bone_map,
+        }
+    }
+
+    /// Shorthand for [`Retargeter::new`] with both rest poses taken from
+    /// [`RestPose::from_scene`].
+    pub fn from_scenes(source: &Scene, target: &Scene, bone_map: HashMap<String, String>) -> Self {
+        Self::new(
+            RestPose::from_scene(source),
+            RestPose::from_scene(target),
+            bone_map,
+        )
+    }
+
+    /// Returns a copy of `animation` whose node channels drive the target
+    /// skeleton.
+    ///
+    /// Mesh and morph channels refer to meshes of the source scene and are
+    /// not carried over.
+    pub fn retarget(&self, animation: &Animation) -> Animation {
+        Animation {
+            name: animation.name.clone(),
+            channels: animation
+                .channels
+                .iter()
+                .filter_map(|channel| self.retarget_channel(channel))
+                .collect(),
+            duration: animation.duration,
+            morph_mesh_channels: vec![],
+            mesh_channels: vec![],
+            ticks_per_second: animation.ticks_per_second,
+        }
+    }
+
+    fn target_name<'a>(&'a self, source: &'a str) -> Option<&'a str> {
+        match self.bone_map.get(source) {
+            Some(target) => Some(target.as_str()),
+            None if self.target.0.contains_key(source) => Some(source),
+            None => None,
+        }
+    }
+
+    fn retarget_channel(&self, channel: &NodeAnim) -> Option<NodeAnim> {
+        let target_name = self.target_name(&channel.name)?;
+        let source = self
+            .source
+            .0
+            .get(&channel.name)
+            .copied()
+            .unwrap_or_default();
+        let target = self.target.0.get(target_name).copied().unwrap_or_default();
+
+        // bones of different length move their children by different amounts
+        let source_length = source.position.length();
+        let ratio = if source_length > f32::EPSILON {
+            target.position.length() / source_length
+        } else {
+            1.0
+        };
+        let rotation_offset = target.rotation * source.rotation.conjugate();
+
+        Some(NodeAnim {
+            name: target_name.to_string(),
+            position_keys: channel
+                .position_keys
+                .iter()
+                .map(|key| VectorKey {
+                    time: key.time,
+                    value: target.position + (key.value - source.position) * ratio,
+                })
+                .collect(),
+            rotation_keys: channel
+                .rotation_keys
+                .iter()
+                .map(|key| QuatKey {
+                    time: key.time,
+                    value: (rotation_offset * key.value).normalize(),
+                })
+                .collect(),
+            scaling_keys: channel
+                .scaling_keys
+                .iter()
+                .map(|key| VectorKey {
+                    time: key.time,
+                    value: Vector3D::new(
+                        rescale(key.value.x, source.scaling.x, target.scaling.x),
+                        rescale(key.value.y, source.scaling.y, target.scaling.y),
+                        rescale(key.value.z, source.scaling.z, target.scaling.z),
+                    ),
+                })
+                .collect(),
+            post_state: channel.post_state,
+            pre_state: channel.pre_state,
+        })
+    }
+}
+
+fn rescale(value: f32, source: f32, target: f32) -> f32 {
+    if source == 0.0 {
+        value
+    } else {
+        value / source * target
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{Animation, NodeAnim, QuatKey, Quaternion, VectorKey},
+        node::Node,
+        retarget::{RestPose, Retargeter},
+        utils, Matrix4x4, Vector3D,
+    };
+    use std::{collections::HashMap, rc::Rc};
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 1e-3,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn skeleton(name: &str, rotation: Quaternion, position: Vector3D) -> Rc<Node> {
+        Rc::new(Node {
+            name: name.to_string(),
+            transformation: Matrix4x4::compose(Vector3D::new(1.0, 1.0, 1.0), rotation, position),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn retarget_compensates_rest_pose() {
+        let half_turn = std::f32::consts::FRAC_1_SQRT_2;
+        let source = skeleton("Hips", Quaternion::identity(), Vector3D::new(0.0, 1.0, 0.0));
+        let target = skeleton(
+            "mixamorig:Hips",
+            Quaternion::new(half_turn, 0.0, half_turn, 0.0),
+            Vector3D::new(0.0, 2.0, 0.0),
+        );
+
+        let animation = Animation {
+            channels: vec![NodeAnim {
+                name: "Hips".to_string(),
+                position_keys: vec![VectorKey {
+                    time: 0.0,
+                    value: Vector3D::new(0.0, 1.5, 0.0),
+                }],
+                rotation_keys: vec![QuatKey {
+                    time: 0.0,
+                    value: Quaternion::new(half_turn, half_turn, 0.0, 0.0),
+                }],
+                scaling_keys: vec![],
+                post_state: 0,
+                pre_state: 0,
+            }],
+            duration: 1.0,
+            ..Default::default()
+        };
+
+        let retargeter = Retargeter::new(
+            RestPose::from_node(&source),
+            RestPose::from_node(&target),
+            HashMap::from([("Hips".to_string(), "mixamorig:Hips".to_string())]),
+        );
+        let retargeted = retargeter.retarget(&animation);
+
+        assert_eq!(1, retargeted.channels.len());
+        assert_eq!(1.0, retargeted.duration);
+
+        let channel = &retargeted.channels[0];
+        assert_eq!("mixamorig:Hips", channel.name);
+        assert_close(3.0, channel.position_keys[0].value.y);
+
+        let expected = Quaternion::new(half_turn, 0.0, half_turn, 0.0)
+            * Quaternion::new(half_turn, half_turn, 0.0, 0.0);
+        let rotation = channel.rotation_keys[0].value;
+        assert_close(expected.w, rotation.w);
+        assert_close(expected.x, rotation.x);
+        assert_close(expected.y, rotation.y);
+        assert_close(expected.z, rotation.z);
+    }
+
+    #[test]
+    fn unmapped_channels_are_dropped() {
+        let source = skeleton("Hips", Quaternion::identity(), Vector3D::default());
+        let target = skeleton("Pelvis", Quaternion::identity(), Vector3D::default());
+
+        let animation = Animation {
+            channels: vec![NodeAnim {
+                name: "Hips".to_string(),
+                position_keys: vec![],
+                rotation_keys: vec![],
+                scaling_keys: vec![],
+                post_state: 0,
+                pre_state: 0,
+            }],
+            ..Default::default()
+        };
+
+        let retargeter = Retargeter::new(
+            RestPose::from_node(&source),
+            RestPose::from_node(&target),
+            HashMap::new(),
+        );
+
+        assert!(retargeter.retarget(&animation).channels.is_empty());
+    }
+
+    #[test]
+    fn retarget_onto_same_skeleton_keeps_keys() {
+        use crate::scene::{PostProcess, Scene};
+
+        let current_directory_buf = utils::get_model("models/FBX/y_bot_run.fbx");
+
+        let scene = Scene::from_file(
+            current_directory_buf.as_str(),
+            vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ],
+        )
+        .unwrap();
+
+        let retargeter = Retargeter::from_scenes(&scene, &scene, HashMap::new());
+        let original = &scene.animations[0];
+        let retargeted = retargeter.retarget(original);
+
+        assert_eq!(original.channels.len(), retargeted.channels.len());
+        assert_eq!(original.duration, retargeted.duration);
+
+        for (original, retargeted) in original.channels.iter().zip(&retargeted.channels) {
+            assert_eq!(original.name, retargeted.name);
+
+            for (original, retargeted) in
+                original.rotation_keys.iter().zip(&retargeted.rotation_keys)
+            {
+                // q and -q describe the same rotation
+                assert_close(1.0, original.value.dot(&retargeted.value).abs());
+            }
+            for (original, retargeted) in
+                original.position_keys.iter().zip(&retargeted.position_keys)
+            {
+                assert_close(original.value.x, retargeted.value.x);
+                assert_close(original.value.y, retargeted.value.y);
+                assert_close(original.value.z, retargeted.value.z);
+            }
+        }
+    }
+}
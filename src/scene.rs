@@ -2,19 +2,21 @@ use crate::material::generate_materials;
 use crate::{
     animation::Animation,
     camera::Camera,
-    fs::{FileOperationsWrapper, FileSystem},
+    fs::{CallbackError, FileOperationsWrapper, FileSystem},
+    importer::Importer,
     light::Light,
+    logging::{self, Severity},
     material::Material,
+    memory::MemoryInfo,
     mesh::Mesh,
     metadata::MetaData,
     node::Node,
+    report::{self, ImportReport},
     sys::*,
     *,
 };
-use std::{
-    ffi::{CStr, CString},
-    rc::Rc,
-};
+use bitflags::bitflags;
+use std::{ffi::CString, io, panic, path::Path, rc::Rc};
 
 use self::property::PropertyStore;
 
@@ -28,7 +30,30 @@ pub struct Scene {
     pub cameras: Vec<Camera>,
     pub lights: Vec<Light>,
     pub root: Option<Rc<Node>>,
-    pub flags: u32,
+    pub flags: SceneFlags,
+}
+
+bitflags! {
+    /// Decoded [`Scene::flags`].
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct SceneFlags: u32 {
+        /// The scene is missing data, e.g. when a file only contains
+        /// animations or materials.
+        const INCOMPLETE = AI_SCENE_FLAGS_INCOMPLETE;
+        /// The validation step ran without finding any problems.
+        const VALIDATED = AI_SCENE_FLAGS_VALIDATED;
+        /// The validation step found issues that are not critical, see the
+        /// log for details.
+        const VALIDATION_WARNING = AI_SCENE_FLAGS_VALIDATION_WARNING;
+        /// Vertices are shared between faces, i.e. the
+        /// [`JoinIdenticalVertices`](PostProcess::JoinIdenticalVertices) step
+        /// ran.
+        const NON_VERBOSE_FORMAT = AI_SCENE_FLAGS_NON_VERBOSE_FORMAT;
+        /// The scene is a pure height-map terrain.
+        const TERRAIN = AI_SCENE_FLAGS_TERRAIN;
+        /// Meshes, materials and other data may be referenced more than once.
+        const ALLOW_SHARED = AI_SCENE_FLAGS_ALLOW_SHARED;
+    }
 }
 
 #[derive(Derivative)]
@@ -64,9 +89,10 @@ pub enum PostProcess {
     /// from the viewer.
     ///
     /// You'll probably want to consider this flag if you use Direct3D for
-    /// rendering. The #aiProcess_ConvertToLeftHanded flag supersedes this
-    /// setting and bundles all conversions typically required for D3D-based
-    /// applications.
+    /// rendering. The
+    /// [`CONVERT_TO_LEFT_HANDED`](PostProcessFlags::CONVERT_TO_LEFT_HANDED)
+    /// preset supersedes this setting and bundles all conversions typically
+    /// required for D3D-based applications.
     MakeLeftHanded = aiPostProcessSteps_aiProcess_MakeLeftHanded as _,
     /// Triangulates all faces of all meshes.
     ///
@@ -183,8 +209,9 @@ pub enum PostProcess {
     /// indices are valid, all animations and bones are linked correctly, all
     /// material references are correct, etc.
     ///
-    /// It is recommended that you capture Assimp's log output if you use this
-    /// flag, so you can easily find out what's wrong if a file fails the
+    /// It is recommended that you capture Assimp's log output (see
+    /// [`logging`](crate::logging)) if you use this flag, so you can easily
+    /// find out what's wrong if a file fails the
     /// validation. The validator is quite strict and will find all
     /// inconsistencies in the data structure... It is recommended that plugin
     /// developers use it to debug their loaders. There are two types of
@@ -241,6 +268,11 @@ pub enum PostProcess {
     /// is recommended to enable this step, although the result is not always
     /// correct.
     FixInfacingNormals = aiPostProcessSteps_aiProcess_FixInfacingNormals as _,
+    /// Links every bone to its node and the root node of its skeleton.
+    ///
+    /// Only assimp's own bone structures get these links, russimp's
+    /// [`Bone`](crate::bone::Bone) finds its node by name instead.
+    PopulateArmatureData = aiPostProcessSteps_aiProcess_PopulateArmatureData as _,
     /// Splits meshes with more than one primitive type in homogeneous
     /// sub-meshes.
     ///
@@ -369,8 +401,8 @@ pub enum PostProcess {
     ///
     /// You’ll probably want to consider this flag if you use Direct3D for
     /// rendering. The
-    /// [`ConvertToLeftHanded`](PostProcess::ConvertToLeftHanded) flag
-    /// supersedes this setting and bundles all conversions typically
+    /// [`CONVERT_TO_LEFT_HANDED`](PostProcessFlags::CONVERT_TO_LEFT_HANDED)
+    /// preset supersedes this setting and bundles all conversions typically
     /// required for Direct3D-based applications.
     FlipUVs = aiPostProcessSteps_aiProcess_FlipUVs as _,
     /// Adjusts the output face winding order to be clockwise (CW).
@@ -409,128 +441,369 @@ pub enum PostProcess {
 
 pub type PostProcessSteps = Vec<PostProcess>;
 
+bitflags! {
+    /// A set of post processing steps, the bitflags version of
+    /// [`PostProcess`]. Every flag is documented on its [`PostProcess`]
+    /// variant.
+    ///
+    /// The import functions take anything that converts into these flags,
+    /// including a `Vec<PostProcess>`:
+    ///
+    /// ```no_run
+    /// use russimp::scene::{PostProcess, PostProcessFlags, Scene};
+    ///
+    /// let scene = Scene::from_file(
+    ///     "models/OBJ/cube.obj",
+    ///     PostProcessFlags::TARGET_REALTIME_QUALITY | PostProcessFlags::FLIP_UVS,
+    /// );
+    /// let scene = Scene::from_file("models/OBJ/cube.obj", vec![PostProcess::Triangulate]);
+    /// ```
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct PostProcessFlags: u32 {
+        const CALCULATE_TANGENT_SPACE = aiPostProcessSteps_aiProcess_CalcTangentSpace as _;
+        const JOIN_IDENTICAL_VERTICES = aiPostProcessSteps_aiProcess_JoinIdenticalVertices as _;
+        const MAKE_LEFT_HANDED = aiPostProcessSteps_aiProcess_MakeLeftHanded as _;
+        const TRIANGULATE = aiPostProcessSteps_aiProcess_Triangulate as _;
+        const REMOVE_COMPONENT = aiPostProcessSteps_aiProcess_RemoveComponent as _;
+        const GENERATE_NORMALS = aiPostProcessSteps_aiProcess_GenNormals as _;
+        const GENERATE_SMOOTH_NORMALS = aiPostProcessSteps_aiProcess_GenSmoothNormals as _;
+        const SPLIT_LARGE_MESHES = aiPostProcessSteps_aiProcess_SplitLargeMeshes as _;
+        const PRE_TRANSFORM_VERTICES = aiPostProcessSteps_aiProcess_PreTransformVertices as _;
+        const LIMIT_BONE_WEIGHTS = aiPostProcessSteps_aiProcess_LimitBoneWeights as _;
+        const VALIDATE_DATA_STRUCTURE = aiPostProcessSteps_aiProcess_ValidateDataStructure as _;
+        const IMPROVE_CACHE_LOCALITY = aiPostProcessSteps_aiProcess_ImproveCacheLocality as _;
+        const REMOVE_REDUNDANT_MATERIALS = aiPostProcessSteps_aiProcess_RemoveRedundantMaterials as _;
+        const FIX_INFACING_NORMALS = aiPostProcessSteps_aiProcess_FixInfacingNormals as _;
+        const POPULATE_ARMATURE_DATA = aiPostProcessSteps_aiProcess_PopulateArmatureData as _;
+        const SORT_BY_PRIMITIVE_TYPE = aiPostProcessSteps_aiProcess_SortByPType as _;
+        const FIND_DEGENERATES = aiPostProcessSteps_aiProcess_FindDegenerates as _;
+        const FIX_OR_REMOVE_INVALID_DATA = aiPostProcessSteps_aiProcess_FindInvalidData as _;
+        const GENERATE_UV_COORDS = aiPostProcessSteps_aiProcess_GenUVCoords as _;
+        const TRANSFORM_UV_COORDS = aiPostProcessSteps_aiProcess_TransformUVCoords as _;
+        const FIND_INSTANCES = aiPostProcessSteps_aiProcess_FindInstances as _;
+        const OPTIMIZE_MESHES = aiPostProcessSteps_aiProcess_OptimizeMeshes as _;
+        const OPTIMIZE_GRAPH = aiPostProcessSteps_aiProcess_OptimizeGraph as _;
+        const FLIP_UVS = aiPostProcessSteps_aiProcess_FlipUVs as _;
+        const FLIP_WINDING_ORDER = aiPostProcessSteps_aiProcess_FlipWindingOrder as _;
+        const SPLIT_BY_BONE_COUNT = aiPostProcessSteps_aiProcess_SplitByBoneCount as _;
+        const DEBONE = aiPostProcessSteps_aiProcess_Debone as _;
+        const GLOBAL_SCALE = aiPostProcessSteps_aiProcess_GlobalScale as _;
+        const EMBED_TEXTURES = aiPostProcessSteps_aiProcess_EmbedTextures as _;
+        const FORCE_GENERATE_NORMALS = aiPostProcessSteps_aiProcess_ForceGenNormals as _;
+        const DROP_NORMALS = aiPostProcessSteps_aiProcess_DropNormals as _;
+        const GENERATE_BOUNDING_BOXES = aiPostProcessSteps_aiProcess_GenBoundingBoxes as _;
+    }
+}
+
+impl PostProcessFlags {
+    /// Converts the right-handed scene assimp returns for use with Direct3D:
+    /// a left-handed coordinate system, flipped texture coordinates and
+    /// clockwise faces.
+    pub const CONVERT_TO_LEFT_HANDED: Self = Self::MAKE_LEFT_HANDED
+        .union(Self::FLIP_UVS)
+        .union(Self::FLIP_WINDING_ORDER);
+
+    /// Assimp's `aiProcessPreset_TargetRealtime_Fast`, for real-time
+    /// rendering when import speed matters more than the result.
+    pub const TARGET_REALTIME_FAST: Self = Self::CALCULATE_TANGENT_SPACE
+        .union(Self::GENERATE_NORMALS)
+        .union(Self::JOIN_IDENTICAL_VERTICES)
+        .union(Self::TRIANGULATE)
+        .union(Self::GENERATE_UV_COORDS)
+        .union(Self::SORT_BY_PRIMITIVE_TYPE);
+
+    /// Assimp's `aiProcessPreset_TargetRealtime_Quality`, a good default for
+    /// real-time rendering.
+    pub const TARGET_REALTIME_QUALITY: Self = Self::CALCULATE_TANGENT_SPACE
+        .union(Self::GENERATE_SMOOTH_NORMALS)
+        .union(Self::JOIN_IDENTICAL_VERTICES)
+        .union(Self::IMPROVE_CACHE_LOCALITY)
+        .union(Self::LIMIT_BONE_WEIGHTS)
+        .union(Self::REMOVE_REDUNDANT_MATERIALS)
+        .union(Self::SPLIT_LARGE_MESHES)
+        .union(Self::TRIANGULATE)
+        .union(Self::GENERATE_UV_COORDS)
+        .union(Self::SORT_BY_PRIMITIVE_TYPE)
+        .union(Self::FIND_DEGENERATES)
+        .union(Self::FIX_OR_REMOVE_INVALID_DATA);
+
+    /// Assimp's `aiProcessPreset_TargetRealtime_MaxQuality`, the quality
+    /// preset plus instancing, mesh optimization and validation.
+    pub const TARGET_REALTIME_MAX_QUALITY: Self = Self::TARGET_REALTIME_QUALITY
+        .union(Self::FIND_INSTANCES)
+        .union(Self::VALIDATE_DATA_STRUCTURE)
+        .union(Self::OPTIMIZE_MESHES);
+
+    /// Steps assimp refuses to run together.
+    const CONFLICTS: [(Self, &'static str); 2] = [
+        (
+            Self::GENERATE_NORMALS.union(Self::GENERATE_SMOOTH_NORMALS),
+            "GenerateNormals and GenerateSmoothNormals",
+        ),
+        (
+            Self::OPTIMIZE_GRAPH.union(Self::PRE_TRANSFORM_VERTICES),
+            "OptimizeGraph and PreTransformVertices",
+        ),
+    ];
+
+    /// Checks the flags like assimp does before it imports a file, so the
+    /// mistake is reported as [`RussimpError::PostProcess`] rather than a
+    /// generic import error. Rejects steps that can't run together, e.g.
+    /// [`GENERATE_NORMALS`](Self::GENERATE_NORMALS) and
+    /// [`GENERATE_SMOOTH_NORMALS`](Self::GENERATE_SMOOTH_NORMALS).
+    pub fn validate(self) -> Russult<()> {
+        for (conflict, steps) in Self::CONFLICTS {
+            if self.contains(conflict) {
+                return Err(RussimpError::PostProcess(format!(
+                    "{} can't be combined",
+                    steps
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<PostProcess> for PostProcessFlags {
+    fn from(step: PostProcess) -> Self {
+        Self::from_bits_retain(step as u32)
+    }
+}
+
+impl From<PostProcessSteps> for PostProcessFlags {
+    fn from(steps: PostProcessSteps) -> Self {
+        steps.into_iter().collect()
+    }
+}
+
+impl FromIterator<PostProcess> for PostProcessFlags {
+    fn from_iter<T: IntoIterator<Item = PostProcess>>(iter: T) -> Self {
+        iter.into_iter().map(Self::from).collect()
+    }
+}
+
+/// A scene imported by assimp that wasn't converted yet. Releases the scene
+/// when dropped.
+pub(crate) struct RawScene(&'static aiScene);
+
+// SAFETY: assimp doesn't keep references to an imported scene, it can be
+// converted and released on any thread
+unsafe impl Send for RawScene {}
+
+impl RawScene {
+    pub(crate) fn convert(self) -> Russult<Scene> {
+        Scene::new(self.0)
+    }
+
+    /// Asks assimp for the memory the scene takes up, which walks the whole
+    /// scene.
+    pub(crate) fn native_memory(&self) -> MemoryInfo {
+        MemoryInfo::native(self.0)
+    }
+}
+
+impl Drop for RawScene {
+    fn drop(&mut self) {
+        Scene::drop_scene(self.0);
+    }
+}
+
 impl Scene {
     fn new(scene: &aiScene) -> Russult<Self> {
         let root = unsafe { scene.mRootNode.as_ref() };
 
         Ok(Self {
             materials: generate_materials(scene)?,
-            meshes: utils::get_vec_from_raw(scene.mMeshes, scene.mNumMeshes),
+            meshes: utils::get_vec_from_raw(scene.mMeshes, scene.mNumMeshes)?,
             metadata: utils::get_raw(scene.mMetaData),
-            animations: utils::get_vec_from_raw(scene.mAnimations, scene.mNumAnimations),
-            cameras: utils::get_vec_from_raw(scene.mCameras, scene.mNumCameras),
-            lights: utils::get_vec_from_raw(scene.mLights, scene.mNumLights),
-            root: root.map(Node::new),
-            flags: scene.mFlags,
+            animations: utils::get_vec_from_raw(scene.mAnimations, scene.mNumAnimations)?,
+            cameras: utils::get_vec_from_raw(scene.mCameras, scene.mNumCameras)?,
+            lights: utils::get_vec_from_raw(scene.mLights, scene.mNumLights)?,
+            root: root.map(Node::new).transpose()?,
+            flags: SceneFlags::from_bits_retain(scene.mFlags),
         })
     }
 
-    pub fn from_file(file_path: &str, flags: PostProcessSteps) -> Russult<Scene> {
-        let bitwise_flag = flags.into_iter().fold(0, |acc, x| acc | (x as u32));
-        let file_path = CString::new(file_path).unwrap();
-        match Scene::get_scene_from_file(file_path, bitwise_flag) {
-            Some(raw_scene) => {
-                let result = Scene::new(raw_scene);
-                Scene::drop_scene(raw_scene);
-                result
-            }
-            None => Err(Scene::get_error()),
+    /// Rejects scenes with the [`SceneFlags::INCOMPLETE`] flag, for callers
+    /// that need meshes and can't use e.g. a file containing only animations.
+    ///
+    /// ```no_run
+    /// use russimp::scene::{PostProcess, Scene};
+    ///
+    /// let scene = Scene::from_file("models/BLEND/box.blend", vec![PostProcess::Triangulate])
+    ///     .and_then(Scene::ensure_complete);
+    /// ```
+    pub fn ensure_complete(self) -> Russult<Scene> {
+        if self.flags.contains(SceneFlags::INCOMPLETE) {
+            return Err(RussimpError::IncompleteScene);
         }
+
+        Ok(self)
+    }
+
+    /// Searches the node hierarchy, including the root node, for a node
+    /// called `name`.
+    pub fn find_node(&self, name: &str) -> Option<Rc<Node>> {
+        let root = self.root.as_ref()?;
+        if root.name == name {
+            return Some(root.clone());
+        }
+
+        root.find(name)
+    }
+
+    /// The node that places `camera` in the scene, which shares its name.
+    pub fn camera_node(&self, camera: &Camera) -> Option<Rc<Node>> {
+        self.find_node(&camera.name)
     }
 
-    pub fn from_file_with_props(
-        file_path: &str,
-        flags: PostProcessSteps,
+    /// The node that places `light` in the scene, which shares its name.
+    pub fn light_node(&self, light: &Light) -> Option<Rc<Node>> {
+        self.find_node(&light.name)
+    }
+
+    /// Imports the file at `file_path`, see [`Importer`] for more options.
+    pub fn from_file<P: AsRef<Path>>(
+        file_path: P,
+        flags: impl Into<PostProcessFlags>,
+    ) -> Russult<Scene> {
+        Importer::new().post_process(flags).read_file(file_path)
+    }
+
+    /// Like [`Scene::from_file`], but also collects the warnings assimp
+    /// logged, the importer it used, how long every step took and the memory
+    /// assimp used for the scene.
+    ///
+    /// Verbose logging is enabled while the file is imported, see
+    /// [`logging::capture`](crate::logging::capture).
+    pub fn from_file_with_report<P: AsRef<Path>>(
+        file_path: P,
+        flags: impl Into<PostProcessFlags>,
+    ) -> Russult<(Scene, ImportReport)> {
+        report::collect(PropertyStore::default(), |props| {
+            Importer::new()
+                .post_process(flags)
+                .borrowed_properties(props)
+                .read_file_raw(file_path)
+        })
+    }
+
+    pub fn from_file_with_props<P: AsRef<Path>>(
+        file_path: P,
+        flags: impl Into<PostProcessFlags>,
         props: &PropertyStore,
     ) -> Russult<Scene> {
-        let bitwise_flag = flags.into_iter().fold(0, |acc, x| acc | (x as u32));
-        let file_path = CString::new(file_path).unwrap();
-        match Scene::get_scene_from_file_with_props(file_path, bitwise_flag, Some(props)) {
-            Some(raw_scene) => {
-                let result = Scene::new(raw_scene);
-                Scene::drop_scene(raw_scene);
-                result
-            }
-            None => Err(Scene::get_error()),
-        }
+        Importer::new()
+            .post_process(flags)
+            .borrowed_properties(props)
+            .read_file(file_path)
     }
 
-    pub fn from_file_system<T: FileSystem>(
-        file_path: &str,
-        flags: PostProcessSteps,
+    /// Imports `file_path` through `file_io`. [`FileSystem::open`] takes
+    /// `&str`, paths that aren't UTF-8 fail with
+    /// [`RussimpError::Io`](crate::RussimpError::Io).
+    pub fn from_file_system<P: AsRef<Path>, T: FileSystem>(
+        file_path: P,
+        flags: impl Into<PostProcessFlags>,
         file_io: &mut T,
     ) -> Russult<Scene> {
-        let bitwise_flag = flags.into_iter().fold(0, |acc, x| acc | (x as u32));
-        let file_path = CString::new(file_path).unwrap();
-        match Scene::get_scene_from_filesystem(file_path, bitwise_flag, file_io) {
-            Some(raw_scene) => {
-                let result = Scene::new(raw_scene);
-                Scene::drop_scene(raw_scene);
-                result
-            }
-            None => Err(Scene::get_error()),
-        }
+        Importer::new()
+            .post_process(flags)
+            .file_system(file_io)
+            .read_file(file_path)
     }
 
-    pub fn from_file_system_with_props<T: FileSystem>(
-        file_path: &str,
-        flags: PostProcessSteps,
+    pub fn from_file_system_with_props<P: AsRef<Path>, T: FileSystem>(
+        file_path: P,
+        flags: impl Into<PostProcessFlags>,
         file_io: &mut T,
         props: &PropertyStore,
     ) -> Russult<Scene> {
-        let bitwise_flag = flags.into_iter().fold(0, |acc, x| acc | (x as u32));
-        let file_path = CString::new(file_path).unwrap();
-        match Scene::get_scene_from_filesystem_with_props(
-            file_path,
-            bitwise_flag,
-            file_io,
-            Some(props),
-        ) {
-            Some(raw_scene) => {
-                let result = Scene::new(raw_scene);
-                Scene::drop_scene(raw_scene);
-                result
-            }
-            None => Err(Scene::get_error()),
-        }
+        Importer::new()
+            .post_process(flags)
+            .file_system(file_io)
+            .borrowed_properties(props)
+            .read_file(file_path)
     }
 
-    pub fn from_buffer(buffer: &[u8], flags: PostProcessSteps, hint: &str) -> Russult<Scene> {
-        let bitwise_flag = flags.into_iter().fold(0, |acc, x| acc | (x as u32));
-        let hint = CString::new(hint).unwrap();
-        match Scene::get_scene_from_file_from_memory(buffer, bitwise_flag, hint) {
-            Some(raw_scene) => {
-                let result = Scene::new(raw_scene);
-                Scene::drop_scene(raw_scene);
-                result
-            }
-            None => Err(Scene::get_error()),
-        }
+    pub fn from_buffer(
+        buffer: &[u8],
+        flags: impl Into<PostProcessFlags>,
+        hint: &str,
+    ) -> Russult<Scene> {
+        Importer::new()
+            .post_process(flags)
+            .read_memory(buffer, hint)
     }
 
     pub fn from_buffer_with_props(
         buffer: &[u8],
-        flags: PostProcessSteps,
+        flags: impl Into<PostProcessFlags>,
         hint: &str,
         props: &PropertyStore,
     ) -> Russult<Scene> {
-        let bitwise_flag = flags.into_iter().fold(0, |acc, x| acc | (x as u32));
-        let hint = CString::new(hint).unwrap();
-        match Scene::get_scene_from_file_from_memory_with_props(
-            buffer,
-            bitwise_flag,
-            hint,
-            Some(props),
-        ) {
-            Some(raw_scene) => {
-                let result = Scene::new(raw_scene);
-                Scene::drop_scene(raw_scene);
-                result
+        Importer::new()
+            .post_process(flags)
+            .borrowed_properties(props)
+            .read_memory(buffer, hint)
+    }
+
+    /// Imports `file_path` through `file_system`, or straight from disk
+    /// without one.
+    pub(crate) fn import_file(
+        file_path: &Path,
+        flags: u32,
+        file_system: Option<&dyn FileSystem>,
+        props: Option<&PropertyStore>,
+    ) -> Russult<RawScene> {
+        let c_path = Scene::path_to_cstring(file_path)?;
+        let (raw_scene, error) = Scene::capture_error(|| match file_system {
+            Some(file_system) => {
+                Scene::get_scene_from_filesystem(c_path, flags, file_system, props)
             }
-            None => Err(Scene::get_error()),
+            None => Ok(Scene::get_scene_from_file(c_path, flags, props)),
+        });
+
+        match raw_scene? {
+            Some(raw_scene) => Ok(RawScene(raw_scene)),
+            None => Err(Scene::import_error(error, Some(file_path))),
+        }
+    }
+
+    pub(crate) fn import_memory(
+        buffer: &[u8],
+        flags: u32,
+        hint: &str,
+        props: Option<&PropertyStore>,
+    ) -> Russult<RawScene> {
+        let hint = Scene::to_cstring(hint)?;
+        let (raw_scene, error) = Scene::capture_error(|| {
+            Scene::get_scene_from_file_from_memory(buffer, flags, hint, props)
+        });
+
+        match raw_scene {
+            Some(raw_scene) => Ok(RawScene(raw_scene)),
+            None => Err(Scene::import_error(error, None)),
         }
     }
 
+    /// Runs a native import and keeps the last error assimp logged on this
+    /// thread. `aiGetErrorString` can't be used, all threads share it.
+    fn capture_error<R>(import: impl FnOnce() -> R) -> (R, Option<String>) {
+        let mut error = None;
+        let result = logging::observe(
+            |message| {
+                if message.severity == Severity::Error {
+                    error = Some(message.message.clone());
+                }
+            },
+            import,
+        );
+
+        (result, error)
+    }
+
     #[inline]
     fn drop_scene(scene: &aiScene) {
         unsafe {
@@ -539,12 +812,7 @@ impl Scene {
     }
 
     #[inline]
-    fn get_scene_from_file<'a>(string: CString, flags: u32) -> Option<&'a aiScene> {
-        Self::get_scene_from_file_with_props(string, flags, None)
-    }
-
-    #[inline]
-    fn get_scene_from_file_with_props<'a>(
+    fn get_scene_from_file<'a>(
         string: CString,
         flags: u32,
         props: Option<&PropertyStore>,
@@ -561,23 +829,14 @@ impl Scene {
     }
 
     #[inline]
-    fn get_scene_from_filesystem<'a, T: FileSystem>(
+    fn get_scene_from_filesystem<'a>(
         string: CString,
         flags: u32,
-        fs: &mut T,
-    ) -> Option<&'a aiScene> {
-        Self::get_scene_from_filesystem_with_props(string, flags, fs, None)
-    }
-
-    #[inline]
-    fn get_scene_from_filesystem_with_props<'a, T: FileSystem>(
-        string: CString,
-        flags: u32,
-        fs: &mut T,
+        fs: &dyn FileSystem,
         props: Option<&PropertyStore>,
-    ) -> Option<&'a aiScene> {
+    ) -> io::Result<Option<&'a aiScene>> {
         let mut file_io = FileOperationsWrapper::new(fs);
-        unsafe {
+        let scene = unsafe {
             aiImportFileExWithProperties(
                 string.as_ptr(),
                 flags,
@@ -585,6 +844,18 @@ impl Scene {
                 props.map(|p| p.as_ptr()).unwrap_or(std::ptr::null_mut()),
             )
             .as_ref()
+        };
+
+        // errors of optional files like textures don't fail an import that succeeded
+        match file_io.take_error() {
+            Some(CallbackError::Panic(payload)) => {
+                if let Some(scene) = scene {
+                    Scene::drop_scene(scene);
+                }
+                panic::resume_unwind(payload)
+            }
+            Some(CallbackError::Io(error)) if scene.is_none() => Err(error),
+            _ => Ok(scene),
         }
     }
 
@@ -593,15 +864,6 @@ impl Scene {
         buffer: &[u8],
         flags: u32,
         hint: CString,
-    ) -> Option<&'a aiScene> {
-        Self::get_scene_from_file_from_memory_with_props(buffer, flags, hint, None)
-    }
-
-    #[inline]
-    fn get_scene_from_file_from_memory_with_props<'a>(
-        buffer: &[u8],
-        flags: u32,
-        hint: CString,
         props: Option<&PropertyStore>,
     ) -> Option<&'a aiScene> {
         unsafe {
@@ -616,18 +878,39 @@ impl Scene {
         }
     }
 
-    fn get_error() -> RussimpError {
-        let error_buf = unsafe { aiGetErrorString() };
-        let error = unsafe { CStr::from_ptr(error_buf).to_string_lossy().into_owned() };
-        RussimpError::Import(error)
+    fn import_error(message: Option<String>, file_path: Option<&Path>) -> RussimpError {
+        RussimpError::Import {
+            message: message.unwrap_or_else(|| "assimp reported no error".to_string()),
+            path: file_path.map(Path::to_path_buf),
+        }
+    }
+
+    fn to_cstring(string: &str) -> Russult<CString> {
+        CString::new(string).map_err(|_| RussimpError::InvalidPath(string.to_string()))
+    }
+
+    /// Assimp takes the bytes of the path on unix, and UTF-8 everywhere else.
+    fn path_to_cstring(path: &Path) -> Russult<CString> {
+        let invalid = || RussimpError::InvalidPath(path.to_string_lossy().into_owned());
+
+        #[cfg(unix)]
+        let bytes = {
+            use std::os::unix::ffi::OsStrExt;
+            path.as_os_str().as_bytes()
+        };
+        #[cfg(not(unix))]
+        let bytes = path.to_str().ok_or_else(invalid)?.as_bytes();
+
+        CString::new(bytes).map_err(|_| invalid())
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::scene::{PostProcess, Scene};
+    use crate::scene::{PostProcess, PostProcessFlags, Scene, SceneFlags};
     use crate::utils;
-    use std::rc::Rc;
+    use crate::RussimpError;
+    use std::{ops::Range, rc::Rc};
 
     #[test]
     fn importing_invalid_file_returns_error() {
@@ -646,6 +929,108 @@ mod test {
         assert!(scene.is_err())
     }
 
+    #[test]
+    fn incomplete_scenes_can_be_rejected() {
+        let scene = || Scene {
+            materials: vec![],
+            meshes: vec![],
+            metadata: None,
+            animations: vec![],
+            cameras: vec![],
+            lights: vec![],
+            root: None,
+            flags: SceneFlags::INCOMPLETE | SceneFlags::VALIDATED,
+        };
+
+        assert!(matches!(
+            scene().ensure_complete(),
+            Err(RussimpError::IncompleteScene)
+        ));
+
+        let complete = Scene {
+            flags: SceneFlags::VALIDATED,
+            ..scene()
+        };
+        assert!(complete.ensure_complete().is_ok());
+    }
+
+    #[test]
+    fn paths_with_nul_bytes_are_rejected() {
+        let result = Scene::from_file("models/box\0.obj", vec![]);
+        assert!(
+            matches!(result, Err(RussimpError::InvalidPath(path)) if path == "models/box\0.obj")
+        );
+
+        let result = Scene::from_buffer(b"", vec![], "o\0bj");
+        assert!(matches!(result, Err(RussimpError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn post_process_flags() {
+        let flags = PostProcessFlags::from(vec![
+            PostProcess::MakeLeftHanded,
+            PostProcess::FlipUVs,
+            PostProcess::FlipWindingOrder,
+        ]);
+        assert_eq!(PostProcessFlags::CONVERT_TO_LEFT_HANDED, flags);
+
+        assert!(PostProcessFlags::TARGET_REALTIME_MAX_QUALITY
+            .contains(PostProcessFlags::TARGET_REALTIME_QUALITY));
+        assert!(PostProcessFlags::TARGET_REALTIME_FAST.validate().is_ok());
+        assert!(PostProcessFlags::TARGET_REALTIME_MAX_QUALITY
+            .validate()
+            .is_ok());
+        assert!((PostProcessFlags::TARGET_REALTIME_FAST
+            | PostProcessFlags::GENERATE_SMOOTH_NORMALS)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn conflicting_steps_are_rejected() {
+        let result = Scene::from_file(
+            utils::get_model("models/OBJ/cube.obj"),
+            vec![
+                PostProcess::GenerateNormals,
+                PostProcess::GenerateSmoothNormals,
+            ],
+        );
+        assert!(matches!(result, Err(RussimpError::PostProcess(_))));
+
+        let result = Scene::from_buffer(
+            b"",
+            PostProcessFlags::OPTIMIZE_GRAPH | PostProcessFlags::PRE_TRANSFORM_VERTICES,
+            "obj",
+        );
+        assert!(matches!(result, Err(RussimpError::PostProcess(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn non_utf8_paths_are_passed_on() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+
+        let path = Path::new(OsStr::from_bytes(b"models/\xff.obj"));
+        let error = Scene::from_file(path, vec![]).unwrap_err();
+
+        assert!(
+            matches!(error, RussimpError::Import { path: Some(error_path), .. } if error_path == path)
+        );
+    }
+
+    #[test]
+    fn import_errors_name_the_file() {
+        let error = Scene::from_file("models/missing.obj", vec![]).unwrap_err();
+
+        assert!(matches!(
+            &error,
+            RussimpError::Import { path: Some(path), .. } if path.ends_with("missing.obj")
+        ));
+        assert!(error
+            .to_string()
+            .starts_with("failed to import models/missing.obj"));
+    }
+
     #[test]
     fn importing_valid_file_returns_scene() {
         let current_directory_buf = utils::get_model("models/BLEND/box.blend");
@@ -661,7 +1046,7 @@ mod test {
         )
         .unwrap();
 
-        assert_eq!(8, scene.flags);
+        assert_eq!(SceneFlags::NON_VERBOSE_FORMAT, scene.flags);
     }
 
     #[test]
@@ -732,4 +1117,61 @@ mod test {
         // Strong refcount must be 1 here, otherwise we leak memory
         assert_eq!(Rc::strong_count(&root), 1);
     }
+
+    /// Deterministic xorshift, so failing inputs can be reproduced.
+    fn corrupt(bytes: &[u8], seed: u64) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let mut bytes = bytes.to_vec();
+        for _ in 0..8 {
+            let index = next() as usize % bytes.len();
+            bytes[index] = next() as u8;
+        }
+        let len = bytes.len() - next() as usize % (bytes.len() / 2);
+        bytes.truncate(len);
+
+        bytes
+    }
+
+    fn import_corrupted_buffers(seeds: Range<u64>) {
+        for (path, hint) in [
+            ("models/OBJ/cube.obj", "obj"),
+            ("models/BLEND/box.blend", "blend"),
+            ("models/FBX/cube_armature.fbx", "fbx"),
+            (
+                "models/GLTF2/BoxTextured-GLTF-Embedded/BoxTextured.gltf",
+                "gltf",
+            ),
+            ("models/COLLADA/blender_cube.dae", "dae"),
+        ] {
+            let bytes = std::fs::read(utils::get_model(path)).unwrap();
+
+            for seed in seeds.clone() {
+                // either outcome is fine, converting the scene must not panic
+                let _ = Scene::from_buffer(
+                    &corrupt(&bytes, seed),
+                    vec![PostProcess::Triangulate, PostProcess::SortByPrimitiveType],
+                    hint,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_buffers_do_not_panic() {
+        import_corrupted_buffers(0..4);
+    }
+
+    // imports 160 corrupted files, run with `cargo test -- --ignored`
+    #[test]
+    #[ignore]
+    fn many_corrupted_buffers_do_not_panic() {
+        import_corrupted_buffers(0..32);
+    }
 }
@@ -0,0 +1,331 @@
+//! The `session` module imports many files with the same options and shares
+//! identical textures and materials between the imported scenes.
+//!
+//! A session doesn't keep an assimp importer alive. Assimp's C API, which
+//! russimp binds, creates and releases a native importer for every file, so
+//! a session only reuses the [`Importer`] configuration and no parser state
+//! or cache carries over from one import to the next. What it saves is the
+//! memory of duplicated textures.
+//!
+//! ```no_run
+//! use russimp::{importer::Importer, scene::PostProcess, session::ImportSession};
+//!
+//! let mut session =
+//!     ImportSession::new(Importer::new().post_process(vec![PostProcess::Triangulate]));
+//!
+//! let first = session.read_file("models/OBJ/cube.obj").unwrap();
+//! let second = session.read_file("models/OBJ/cube.obj").unwrap();
+//! assert!(std::rc::Rc::ptr_eq(&first.materials[0], &second.materials[0]));
+//! ```
+use crate::{
+    importer::Importer,
+    material::{DataContent, Material, MaterialProperty, PropertyTypeInfo, Texture},
+    scene::Scene,
+    Russult,
+};
+use derivative::Derivative;
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::Path,
+    rc::Rc,
+};
+
+/// A scene imported by an [`ImportSession`].
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct SessionScene {
+    /// The imported scene. Its materials are copies of
+    /// [`materials`](SessionScene::materials), so the scene stays consistent
+    /// on its own. A copy clones the material properties, which are small,
+    /// while the textures stay shared, see [`ImportSession`].
+    pub scene: Scene,
+    /// The materials of the scene in the order of
+    /// [`Mesh::material_index`](crate::mesh::Mesh::material_index), shared
+    /// with identical materials of the other scenes of the session.
+    pub materials: Vec<Rc<Material>>,
+}
+
+/// Imports files with one [`Importer`] and deduplicates their textures and
+/// materials by content.
+///
+/// Textures are compared by size, format hint and data, a shared texture
+/// keeps the filename of the scene that was imported first. As textures are
+/// shared through a `RefCell`, changing one changes it for every scene of the
+/// session.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct ImportSession<'a> {
+    importer: Importer<'a>,
+    #[derivative(Debug = "ignore")]
+    textures: HashMap<u64, Vec<Rc<RefCell<Texture>>>>,
+    #[derivative(Debug = "ignore")]
+    materials: HashMap<u64, Vec<Rc<Material>>>,
+}
+
+impl<'a> ImportSession<'a> {
+    pub fn new(importer: Importer<'a>) -> Self {
+        Self {
+            importer,
+            textures: HashMap::new(),
+            materials: HashMap::new(),
+        }
+    }
+
+    pub fn read_file<P: AsRef<Path>>(&mut self, file_path: P) -> Russult<SessionScene> {
+        let scene = self.importer.read_file(file_path)?;
+        Ok(self.share(scene))
+    }
+
+    /// Imports a file from `buffer`, see [`Importer::read_memory`].
+    pub fn read_memory(&mut self, buffer: &[u8], hint: &str) -> Russult<SessionScene> {
+        let scene = self.importer.read_memory(buffer, hint)?;
+        Ok(self.share(scene))
+    }
+
+    /// The number of distinct textures imported so far.
+    pub fn texture_count(&self) -> usize {
+        self.textures.values().map(Vec::len).sum()
+    }
+
+    /// The number of distinct materials imported so far.
+    pub fn material_count(&self) -> usize {
+        self.materials.values().map(Vec::len).sum()
+    }
+
+    /// Forgets the textures and materials of the scenes imported so far, they
+    /// are no longer shared with later scenes.
+    pub fn clear(&mut self) {
+        self.textures.clear();
+        self.materials.clear();
+    }
+
+    fn share(&mut self, mut scene: Scene) -> SessionScene {
+        let materials: Vec<_> = std::mem::take(&mut scene.materials)
+            .into_iter()
+            .map(|mut material| {
+                for texture in material.textures.values_mut() {
+                    *texture = self.share_texture(texture);
+                }
+                self.share_material(material)
+            })
+            .collect();
+        scene.materials = materials
+            .iter()
+            .map(|material| Material::clone(material))
+            .collect();
+
+        SessionScene { scene, materials }
+    }
+
+    fn share_texture(&mut self, texture: &Rc<RefCell<Texture>>) -> Rc<RefCell<Texture>> {
+        let candidates = self
+            .textures
+            .entry(hash_texture(&texture.borrow()))
+            .or_default();
+        if let Some(shared) = candidates.iter().find(|shared| {
+            Rc::ptr_eq(shared, texture) || same_texture(&shared.borrow(), &texture.borrow())
+        }) {
+            return shared.clone();
+        }
+
+        candidates.push(texture.clone());
+        texture.clone()
+    }
+
+    fn share_material(&mut self, material: Material) -> Rc<Material> {
+        let candidates = self.materials.entry(hash_material(&material)).or_default();
+        if let Some(shared) = candidates
+            .iter()
+            .find(|shared| same_material(shared, &material))
+        {
+            return shared.clone();
+        }
+
+        let material = Rc::new(material);
+        candidates.push(material.clone());
+        material
+    }
+}
+
+fn hash_texture(texture: &Texture) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    texture.width.hash(&mut hasher);
+    texture.height.hash(&mut hasher);
+    texture.ach_format_hint.hash(&mut hasher);
+    match &texture.data {
+        DataContent::Texel(texels) => {
+            for texel in texels {
+                [texel.b, texel.g, texel.r, texel.a].hash(&mut hasher);
+            }
+        }
+        DataContent::Bytes(bytes) => bytes.hash(&mut hasher),
+    }
+    hasher.finish()
+}
+
+fn same_texture(a: &Texture, b: &Texture) -> bool {
+    let same_data = match (&a.data, &b.data) {
+        (DataContent::Texel(a), DataContent::Texel(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(a, b)| [a.b, a.g, a.r, a.a] == [b.b, b.g, b.r, b.a])
+        }
+        (DataContent::Bytes(a), DataContent::Bytes(b)) => a == b,
+        _ => false,
+    };
+
+    a.width == b.width
+        && a.height == b.height
+        && a.ach_format_hint == b.ach_format_hint
+        && same_data
+}
+
+fn hash_property(property: &MaterialProperty, hasher: &mut DefaultHasher) {
+    property.key.hash(hasher);
+    property.index.hash(hasher);
+    property.semantic.hash(hasher);
+    match &property.data {
+        PropertyTypeInfo::Buffer(data) => data.hash(hasher),
+        PropertyTypeInfo::IntegerArray(data) => data.hash(hasher),
+        PropertyTypeInfo::FloatArray(data) => {
+            for value in data {
+                value.to_bits().hash(hasher);
+            }
+        }
+        PropertyTypeInfo::String(data) => data.hash(hasher),
+    }
+}
+
+/// Materials are hashed after their textures were shared, so the textures
+/// are compared by address.
+fn hash_material(material: &Material) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for property in &material.properties {
+        hash_property(property, &mut hasher);
+    }
+
+    // the order of a hash map is random, combine the textures independently
+    let textures = material
+        .textures
+        .iter()
+        .map(|(texture_type, texture)| {
+            let mut hasher = DefaultHasher::new();
+            texture_type.hash(&mut hasher);
+            Rc::as_ptr(texture).hash(&mut hasher);
+            hasher.finish()
+        })
+        .fold(0, u64::wrapping_add);
+    textures.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn same_material(a: &Material, b: &Material) -> bool {
+    let same_properties = a.properties.len() == b.properties.len()
+        && a.properties.iter().zip(&b.properties).all(|(a, b)| {
+            a.key == b.key && a.index == b.index && a.semantic == b.semantic && a.data == b.data
+        });
+    let same_textures = a.textures.len() == b.textures.len()
+        && a.textures.iter().all(|(texture_type, texture)| {
+            b.textures
+                .get(texture_type)
+                .is_some_and(|other| Rc::ptr_eq(texture, other))
+        });
+
+    same_properties && same_textures
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        importer::Importer,
+        material::{
+            DataContent, Material, MaterialProperty, PropertyTypeInfo, Texture, TextureType,
+        },
+        scene::{PostProcess, Scene, SceneFlags},
+        session::ImportSession,
+        utils,
+    };
+    use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+    fn scene(name: &str, color: f32) -> Scene {
+        let texture = Texture {
+            height: 0,
+            width: 4,
+            filename: name.to_string(),
+            ach_format_hint: "png".to_string(),
+            data: DataContent::Bytes(vec![1, 2, 3, 4]),
+        };
+        let material = Material {
+            properties: vec![MaterialProperty {
+                key: "$clr.diffuse".to_string(),
+                data: PropertyTypeInfo::FloatArray(vec![color, color, color]),
+                index: 0,
+                semantic: TextureType::None,
+            }],
+            textures: HashMap::from([(TextureType::Diffuse, Rc::new(RefCell::new(texture)))]),
+        };
+
+        Scene {
+            materials: vec![material],
+            meshes: vec![],
+            metadata: None,
+            animations: vec![],
+            cameras: vec![],
+            lights: vec![],
+            root: None,
+            flags: SceneFlags::empty(),
+        }
+    }
+
+    #[test]
+    fn identical_resources_are_shared() {
+        let mut session = ImportSession::new(Importer::new());
+
+        let first = session.share(scene("*0", 1.0));
+        let second = session.share(scene("*1", 1.0));
+        let third = session.share(scene("*0", 0.5));
+
+        assert!(Rc::ptr_eq(&first.materials[0], &second.materials[0]));
+        assert!(!Rc::ptr_eq(&first.materials[0], &third.materials[0]));
+        assert!(Rc::ptr_eq(
+            &first.materials[0].textures[&TextureType::Diffuse],
+            &third.materials[0].textures[&TextureType::Diffuse]
+        ));
+        assert_eq!(1, second.scene.materials.len());
+        assert!(Rc::ptr_eq(
+            &second.materials[0].textures[&TextureType::Diffuse],
+            &second.scene.materials[0].textures[&TextureType::Diffuse]
+        ));
+        assert_eq!(1, session.texture_count());
+        assert_eq!(2, session.material_count());
+
+        session.clear();
+        let fourth = session.share(scene("*0", 1.0));
+        assert!(!Rc::ptr_eq(&first.materials[0], &fourth.materials[0]));
+    }
+
+    #[test]
+    fn read_the_same_file_twice() {
+        let current_directory_buf = utils::get_model("models/OBJ/cube.obj");
+        let mut session = ImportSession::new(Importer::new().post_process(vec![
+            PostProcess::CalculateTangentSpace,
+            PostProcess::Triangulate,
+            PostProcess::JoinIdenticalVertices,
+            PostProcess::SortByPrimitiveType,
+        ]));
+
+        let first = session.read_file(&current_directory_buf).unwrap();
+        let second = session.read_file(&current_directory_buf).unwrap();
+
+        assert_eq!(2, first.materials.len());
+        assert_eq!(2, first.scene.materials.len());
+        assert!(first.scene.validate().is_empty());
+        for (first, second) in first.materials.iter().zip(&second.materials) {
+            assert!(Rc::ptr_eq(first, second));
+        }
+        assert_eq!(2, session.material_count());
+    }
+}
@@ -0,0 +1,585 @@
+//! The `validate` module checks the consistency of a [`Scene`], e.g. one
+//! that was built or edited in Rust. Assimp's
+//! [`ValidateDataStructure`](crate::scene::PostProcess::ValidateDataStructure)
+//! step only checks scenes while they are imported.
+//!
+//! ```no_run
+//! use russimp::scene::{PostProcess, Scene};
+//!
+//! let mut scene = Scene::from_file("models/OBJ/cube.obj", vec![PostProcess::Triangulate]).unwrap();
+//! scene.meshes[0].material_index = 42;
+//!
+//! for issue in scene.validate() {
+//!     println!("{}", issue);
+//! }
+//! ```
+use crate::{
+    animation::Quaternion, mesh::Mesh, node::Node, scene::Scene, Color4D, Matrix4x4, Vector3D,
+};
+use derivative::Derivative;
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+/// The exclusive bounds of the bone weight sums assimp's
+/// `ValidateDataStructure` step accepts without a warning. Like assimp, sums
+/// of 0 aren't reported.
+const WEIGHT_SUM_MIN: f32 = 0.94;
+const WEIGHT_SUM_MAX: f32 = 1.05;
+
+/// A per vertex attribute of a [`Mesh`].
+#[derive(Clone, Copy, Derivative, PartialEq, Eq)]
+#[derivative(Debug)]
+pub enum MeshAttribute {
+    Vertices,
+    Normals,
+    Tangents,
+    Bitangents,
+    /// The vertex colors of the given channel.
+    Colors(usize),
+    /// The texture coordinates of the given channel.
+    TextureCoords(usize),
+}
+
+/// A problem found by [`Scene::validate`]. Meshes are identified by their
+/// index in [`Scene::meshes`].
+#[derive(Clone, Derivative, PartialEq)]
+#[derivative(Debug)]
+#[non_exhaustive]
+pub enum ValidationIssue {
+    /// A node references a mesh past the end of [`Scene::meshes`].
+    NodeMeshOutOfBounds { node: String, mesh: u32 },
+    /// A mesh references a material past the end of [`Scene::materials`].
+    MaterialOutOfBounds { mesh: usize, material: u32 },
+    /// A face references a vertex past the end of [`Mesh::vertices`].
+    FaceIndexOutOfBounds {
+        mesh: usize,
+        face: usize,
+        index: u32,
+    },
+    /// An attribute doesn't have one value per vertex.
+    AttributeLength {
+        mesh: usize,
+        attribute: MeshAttribute,
+        len: usize,
+        vertices: usize,
+    },
+    /// A bone weight references a vertex past the end of [`Mesh::vertices`].
+    BoneVertexOutOfBounds {
+        mesh: usize,
+        bone: String,
+        vertex: u32,
+    },
+    /// The bone weights of a vertex add up to neither 0 nor roughly 1, with
+    /// the bounds assimp checks.
+    BoneWeightSum { mesh: usize, vertex: u32, sum: f32 },
+    /// An animation channel animates a node that isn't in the scene.
+    UnknownChannelNode { animation: String, node: String },
+    /// An attribute contains NaN or an infinite value, `index` is the first
+    /// vertex with one.
+    NonFiniteAttribute {
+        mesh: usize,
+        attribute: MeshAttribute,
+        index: usize,
+    },
+    /// The transformation of a node contains NaN or an infinite value.
+    NonFiniteTransformation { node: String },
+    /// A key of an animation channel contains NaN or an infinite value, `time`
+    /// is the time of the first key with one.
+    NonFiniteKey {
+        animation: String,
+        node: String,
+        time: f64,
+    },
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::NodeMeshOutOfBounds { node, mesh } => {
+                write!(f, "node {} references missing mesh {}", node, mesh)
+            }
+            ValidationIssue::MaterialOutOfBounds { mesh, material } => {
+                write!(f, "mesh {} references missing material {}", mesh, material)
+            }
+            ValidationIssue::FaceIndexOutOfBounds { mesh, face, index } => write!(
+                f,
+                "face {} of mesh {} references missing vertex {}",
+                face, mesh, index
+            ),
+            ValidationIssue::AttributeLength {
+                mesh,
+                attribute,
+                len,
+                vertices,
+            } => write!(
+                f,
+                "mesh {} has {} {:?} for {} vertices",
+                mesh, len, attribute, vertices
+            ),
+            ValidationIssue::BoneVertexOutOfBounds { mesh, bone, vertex } => write!(
+                f,
+                "bone {} of mesh {} references missing vertex {}",
+                bone, mesh, vertex
+            ),
+            ValidationIssue::BoneWeightSum { mesh, vertex, sum } => write!(
+                f,
+                "bone weights of vertex {} of mesh {} add up to {}",
+                vertex, mesh, sum
+            ),
+            ValidationIssue::UnknownChannelNode { animation, node } => {
+                write!(f, "animation {} animates missing node {}", animation, node)
+            }
+            ValidationIssue::NonFiniteAttribute {
+                mesh,
+                attribute,
+                index,
+            } => write!(
+                f,
+                "{:?} of mesh {} are not finite at vertex {}",
+                attribute, mesh, index
+            ),
+            ValidationIssue::NonFiniteTransformation { node } => {
+                write!(f, "transformation of node {} is not finite", node)
+            }
+            ValidationIssue::NonFiniteKey {
+                animation,
+                node,
+                time,
+            } => write!(
+                f,
+                "key at {} of node {} in animation {} is not finite",
+                time, node, animation
+            ),
+        }
+    }
+}
+
+impl Scene {
+    /// Checks that the indices of the scene are in bounds, that every mesh
+    /// attribute has one value per vertex, that bone weights add up to
+    /// roughly 1, within the bounds assimp checks, that animations only
+    /// animate nodes of the scene, and that there are no NaN or infinite
+    /// values.
+    ///
+    /// Returns every issue found, an empty list for a valid scene.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = vec![];
+        let mut node_names = HashSet::new();
+        if let Some(root) = &self.root {
+            self.validate_node(root, &mut node_names, &mut issues);
+        }
+
+        for (index, mesh) in self.meshes.iter().enumerate() {
+            if mesh.material_index as usize >= self.materials.len() {
+                issues.push(ValidationIssue::MaterialOutOfBounds {
+                    mesh: index,
+                    material: mesh.material_index,
+                });
+            }
+
+            validate_mesh(index, mesh, &mut issues);
+        }
+
+        for animation in &self.animations {
+            for channel in &animation.channels {
+                if !node_names.contains(channel.name.as_str()) {
+                    issues.push(ValidationIssue::UnknownChannelNode {
+                        animation: animation.name.clone(),
+                        node: channel.name.clone(),
+                    });
+                }
+
+                let positions = channel.position_keys.iter();
+                let scalings = channel.scaling_keys.iter();
+                let non_finite = positions
+                    .chain(scalings)
+                    .filter(|key| !key.time.is_finite() || !vector_is_finite(&key.value))
+                    .map(|key| key.time)
+                    .chain(
+                        channel
+                            .rotation_keys
+                            .iter()
+                            .filter(|key| {
+                                !key.time.is_finite() || !quaternion_is_finite(&key.value)
+                            })
+                            .map(|key| key.time),
+                    )
+                    .reduce(f64::min);
+                if let Some(time) = non_finite {
+                    issues.push(ValidationIssue::NonFiniteKey {
+                        animation: animation.name.clone(),
+                        node: channel.name.clone(),
+                        time,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    fn validate_node(
+        &self,
+        node: &Node,
+        names: &mut HashSet<String>,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        names.insert(node.name.clone());
+        for &mesh in &node.meshes {
+            if mesh as usize >= self.meshes.len() {
+                issues.push(ValidationIssue::NodeMeshOutOfBounds {
+                    node: node.name.clone(),
+                    mesh,
+                });
+            }
+        }
+
+        if !matrix_is_finite(&node.transformation) {
+            issues.push(ValidationIssue::NonFiniteTransformation {
+                node: node.name.clone(),
+            });
+        }
+
+        for child in node.children.borrow().iter() {
+            self.validate_node(child, names, issues);
+        }
+    }
+}
+
+fn validate_mesh(index: usize, mesh: &Mesh, issues: &mut Vec<ValidationIssue>) {
+    let vertices = mesh.vertices.len();
+
+    for (face_index, face) in mesh.faces.iter().enumerate() {
+        for &vertex in face.0.iter().filter(|&&vertex| vertex as usize >= vertices) {
+            issues.push(ValidationIssue::FaceIndexOutOfBounds {
+                mesh: index,
+                face: face_index,
+                index: vertex,
+            });
+        }
+    }
+
+    let mut check = |attribute, len: usize, first_non_finite: Option<usize>| {
+        if attribute != MeshAttribute::Vertices && len != vertices {
+            issues.push(ValidationIssue::AttributeLength {
+                mesh: index,
+                attribute,
+                len,
+                vertices,
+            });
+        }
+
+        if let Some(vertex) = first_non_finite {
+            issues.push(ValidationIssue::NonFiniteAttribute {
+                mesh: index,
+                attribute,
+                index: vertex,
+            });
+        }
+    };
+    let first_non_finite = |values: &[Vector3D]| values.iter().position(|v| !vector_is_finite(v));
+
+    check(
+        MeshAttribute::Vertices,
+        vertices,
+        first_non_finite(&mesh.vertices),
+    );
+    // normals and tangents are optional, but must cover every vertex if present
+    for (attribute, values) in [
+        (MeshAttribute::Normals, &mesh.normals),
+        (MeshAttribute::Tangents, &mesh.tangents),
+        (MeshAttribute::Bitangents, &mesh.bitangents),
+    ] {
+        if !values.is_empty() {
+            check(attribute, values.len(), first_non_finite(values));
+        }
+    }
+
+    for (channel, colors) in mesh.colors.iter().enumerate() {
+        if let Some(colors) = colors {
+            let non_finite = colors.iter().position(|color| !color_is_finite(color));
+            check(MeshAttribute::Colors(channel), colors.len(), non_finite);
+        }
+    }
+
+    for (channel, coords) in mesh.texture_coords.iter().enumerate() {
+        if let Some(coords) = coords {
+            let non_finite = first_non_finite(coords);
+            check(
+                MeshAttribute::TextureCoords(channel),
+                coords.len(),
+                non_finite,
+            );
+        }
+    }
+
+    let mut weight_sums = vec![None; vertices];
+    for bone in &mesh.bones {
+        for weight in &bone.weights {
+            match weight_sums.get_mut(weight.vertex_id as usize) {
+                Some(sum) => *sum.get_or_insert(0.0) += weight.weight,
+                None => issues.push(ValidationIssue::BoneVertexOutOfBounds {
+                    mesh: index,
+                    bone: bone.name.clone(),
+                    vertex: weight.vertex_id,
+                }),
+            }
+        }
+    }
+
+    for (vertex, sum) in weight_sums.into_iter().enumerate() {
+        let Some(sum) = sum else { continue };
+        if !sum.is_finite() || (sum != 0.0 && (sum <= WEIGHT_SUM_MIN || sum >= WEIGHT_SUM_MAX)) {
+            issues.push(ValidationIssue::BoneWeightSum {
+                mesh: index,
+                vertex: vertex as u32,
+                sum,
+            });
+        }
+    }
+}
+
+fn vector_is_finite(vector: &Vector3D) -> bool {
+    vector.x.is_finite() && vector.y.is_finite() && vector.z.is_finite()
+}
+
+fn quaternion_is_finite(quaternion: &Quaternion) -> bool {
+    [quaternion.w, quaternion.x, quaternion.y, quaternion.z]
+        .iter()
+        .all(|value| value.is_finite())
+}
+
+fn color_is_finite(color: &Color4D) -> bool {
+    [color.r, color.g, color.b, color.a]
+        .iter()
+        .all(|value| value.is_finite())
+}
+
+fn matrix_is_finite(m: &Matrix4x4) -> bool {
+    [
+        m.a1, m.a2, m.a3, m.a4, m.b1, m.b2, m.b3, m.b4, m.c1, m.c2, m.c3, m.c4, m.d1, m.d2, m.d3,
+        m.d4,
+    ]
+    .iter()
+    .all(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{Animation, NodeAnim, QuatKey, Quaternion, VectorKey},
+        bone::{Bone, VertexWeight},
+        face::Face,
+        material::Material,
+        mesh::Mesh,
+        node::Node,
+        scene::{PostProcess, Scene, SceneFlags},
+        utils,
+        validate::{MeshAttribute, ValidationIssue},
+        Vector3D,
+    };
+    use std::rc::Rc;
+
+    fn triangle() -> Mesh {
+        Mesh {
+            vertices: vec![
+                Vector3D::new(0.0, 0.0, 0.0),
+                Vector3D::new(1.0, 0.0, 0.0),
+                Vector3D::new(0.0, 1.0, 0.0),
+            ],
+            normals: vec![Vector3D::new(0.0, 0.0, 1.0); 3],
+            faces: vec![Face(vec![0, 1, 2])],
+            bones: vec![Bone {
+                weights: (0..3)
+                    .map(|vertex_id| VertexWeight {
+                        weight: 1.0,
+                        vertex_id,
+                    })
+                    .collect(),
+                name: "Root".to_string(),
+                ..Bone::default()
+            }],
+            ..Mesh::default()
+        }
+    }
+
+    fn scene(mesh: Mesh, channel: &str) -> Scene {
+        let root = Node {
+            name: "Root".to_string(),
+            meshes: vec![0],
+            ..Node::default()
+        };
+        let channel = NodeAnim {
+            name: channel.to_string(),
+            position_keys: vec![VectorKey {
+                time: 0.0,
+                value: Vector3D::default(),
+            }],
+            rotation_keys: vec![QuatKey {
+                time: 0.0,
+                value: Quaternion::identity(),
+            }],
+            scaling_keys: vec![],
+            post_state: 0,
+            pre_state: 0,
+        };
+
+        Scene {
+            materials: vec![Material {
+                properties: vec![],
+                textures: Default::default(),
+            }],
+            meshes: vec![mesh],
+            metadata: None,
+            animations: vec![Animation {
+                name: "Wave".to_string(),
+                channels: vec![channel],
+                ..Animation::default()
+            }],
+            cameras: vec![],
+            lights: vec![],
+            root: Some(Rc::new(root)),
+            flags: SceneFlags::empty(),
+        }
+    }
+
+    #[test]
+    fn valid_scene_has_no_issues() {
+        assert_eq!(
+            Vec::<ValidationIssue>::new(),
+            scene(triangle(), "Root").validate()
+        );
+
+        let mut mesh = triangle();
+        mesh.bones[0].weights[0].weight = 0.95;
+        mesh.bones[0].weights[1].weight = 1.04;
+        mesh.bones[0].weights[2].weight = 0.0;
+        assert_eq!(
+            Vec::<ValidationIssue>::new(),
+            scene(mesh, "Root").validate()
+        );
+    }
+
+    #[test]
+    fn weight_sums_on_the_bounds_are_reported() {
+        let mut mesh = triangle();
+        mesh.bones[0].weights[0].weight = 0.94;
+        mesh.bones[0].weights[1].weight = 1.05;
+
+        assert_eq!(
+            vec![
+                ValidationIssue::BoneWeightSum {
+                    mesh: 0,
+                    vertex: 0,
+                    sum: 0.94
+                },
+                ValidationIssue::BoneWeightSum {
+                    mesh: 0,
+                    vertex: 1,
+                    sum: 1.05
+                },
+            ],
+            scene(mesh, "Root").validate()
+        );
+    }
+
+    #[test]
+    fn every_issue_is_reported() {
+        let mut mesh = triangle();
+        mesh.material_index = 1;
+        mesh.faces.push(Face(vec![0, 2, 3]));
+        mesh.normals.pop();
+        mesh.texture_coords = vec![Some(vec![Vector3D::default(); 3])];
+        mesh.texture_coords[0].as_mut().unwrap()[1].x = f32::NAN;
+        mesh.bones[0].weights[1].weight = 0.5;
+        mesh.bones[0].weights.push(VertexWeight {
+            weight: 1.0,
+            vertex_id: 7,
+        });
+
+        let mut scene = scene(mesh, "Arm");
+        scene
+            .root
+            .as_ref()
+            .unwrap()
+            .children
+            .replace(vec![Rc::new(Node {
+                name: "Child".to_string(),
+                meshes: vec![3],
+                ..Node::default()
+            })]);
+        scene.animations[0].channels[0].rotation_keys[0].value.w = f32::INFINITY;
+
+        let issues = scene.validate();
+
+        let expected = [
+            ValidationIssue::NodeMeshOutOfBounds {
+                node: "Child".to_string(),
+                mesh: 3,
+            },
+            ValidationIssue::MaterialOutOfBounds {
+                mesh: 0,
+                material: 1,
+            },
+            ValidationIssue::FaceIndexOutOfBounds {
+                mesh: 0,
+                face: 1,
+                index: 3,
+            },
+            ValidationIssue::AttributeLength {
+                mesh: 0,
+                attribute: MeshAttribute::Normals,
+                len: 2,
+                vertices: 3,
+            },
+            ValidationIssue::NonFiniteAttribute {
+                mesh: 0,
+                attribute: MeshAttribute::TextureCoords(0),
+                index: 1,
+            },
+            ValidationIssue::BoneVertexOutOfBounds {
+                mesh: 0,
+                bone: "Root".to_string(),
+                vertex: 7,
+            },
+            ValidationIssue::BoneWeightSum {
+                mesh: 0,
+                vertex: 1,
+                sum: 0.5,
+            },
+            ValidationIssue::UnknownChannelNode {
+                animation: "Wave".to_string(),
+                node: "Arm".to_string(),
+            },
+            ValidationIssue::NonFiniteKey {
+                animation: "Wave".to_string(),
+                node: "Arm".to_string(),
+                time: 0.0,
+            },
+        ];
+        assert_eq!(expected.len(), issues.len());
+        for issue in expected {
+            assert!(issues.contains(&issue), "missing {}", issue);
+        }
+    }
+
+    #[test]
+    fn imported_scene_is_valid() {
+        let current_directory_buf = utils::get_model("models/OBJ/cube.obj");
+
+        let scene = Scene::from_file(
+            current_directory_buf.as_str(),
+            vec![
+                PostProcess::CalculateTangentSpace,
+                PostProcess::Triangulate,
+                PostProcess::JoinIdenticalVertices,
+                PostProcess::SortByPrimitiveType,
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(Vec::<ValidationIssue>::new(), scene.validate());
+    }
+}
@@ -0,0 +1,140 @@
+//! The `version` module describes the assimp build russimp runs on, for
+//! example to include it in bug reports:
+//!
+//! ```no_run
+//! println!("{}", russimp::version());
+//! ```
+use crate::{
+    sys::{
+        aiGetBranchName, aiGetCompileFlags, aiGetExportFormatCount, aiGetLegalString,
+        aiGetVersionMajor, aiGetVersionMinor, aiGetVersionPatch, aiGetVersionRevision,
+    },
+    utils,
+};
+use bitflags::bitflags;
+use derivative::Derivative;
+use std::fmt::{self, Display, Formatter};
+
+bitflags! {
+    /// Decoded [`Version::compile_flags`], assimp's `ASSIMP_CFLAGS_*`.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct CompileFlags: u32 {
+        /// Assimp is a shared library.
+        const SHARED = 0x1;
+        /// Assimp was built against STLport.
+        const STLPORT = 0x2;
+        /// Assimp is a debug build.
+        const DEBUG = 0x4;
+        /// Assimp was built without boost.
+        const NO_BOOST = 0x8;
+        /// Assimp was built without thread support.
+        const SINGLE_THREADED = 0x10;
+        /// Assimp uses doubles instead of floats for its math types.
+        const DOUBLE_SUPPORT = 0x20;
+    }
+}
+
+/// The russimp-sys features russimp was compiled with.
+#[derive(Clone, Copy, Default, Derivative, PartialEq, Eq)]
+#[derivative(Debug)]
+pub struct SysFeatures {
+    /// Assimp was downloaded as a prebuilt library instead of being found on
+    /// the system.
+    pub prebuilt: bool,
+    /// Assimp is linked statically.
+    pub static_link: bool,
+    /// Assimp was built without zlib.
+    pub nozlib: bool,
+}
+
+#[derive(Clone, Derivative, PartialEq, Eq)]
+#[derivative(Debug)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// The git commit assimp was built from.
+    pub revision: u32,
+    pub branch: String,
+    pub compile_flags: CompileFlags,
+    /// Whether assimp was built with its exporters.
+    pub exporters: bool,
+    pub features: SysFeatures,
+    /// Assimp's copyright notice.
+    #[derivative(Debug = "ignore")]
+    pub legal: String,
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "assimp {}.{}.{} (revision {:x}",
+            self.major, self.minor, self.patch, self.revision
+        )?;
+        if !self.branch.is_empty() {
+            write!(f, ", branch {}", self.branch)?;
+        }
+
+        write!(f, ", flags {:?}", self.compile_flags)?;
+        if !self.exporters {
+            write!(f, ", no exporters")?;
+        }
+
+        write!(f, ", features {:?})", self.features)
+    }
+}
+
+/// The version and build configuration of the assimp library russimp uses.
+pub fn version() -> Version {
+    unsafe {
+        Version {
+            major: aiGetVersionMajor(),
+            minor: aiGetVersionMinor(),
+            patch: aiGetVersionPatch(),
+            revision: aiGetVersionRevision(),
+            branch: utils::get_string(aiGetBranchName()),
+            compile_flags: CompileFlags::from_bits_retain(aiGetCompileFlags()),
+            exporters: aiGetExportFormatCount() > 0,
+            features: SysFeatures {
+                prebuilt: cfg!(feature = "prebuilt"),
+                static_link: cfg!(feature = "static-link"),
+                nozlib: cfg!(feature = "nozlib"),
+            },
+            legal: utils::get_string(aiGetLegalString()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::version::{self, CompileFlags, SysFeatures, Version};
+
+    #[test]
+    fn version_describes_itself() {
+        let version = Version {
+            major: 5,
+            minor: 2,
+            patch: 5,
+            revision: 0x9b3b6a7,
+            branch: "master".to_string(),
+            compile_flags: CompileFlags::SHARED | CompileFlags::DOUBLE_SUPPORT,
+            exporters: false,
+            features: SysFeatures::default(),
+            legal: String::new(),
+        };
+
+        let description = version.to_string();
+        assert!(description.starts_with("assimp 5.2.5 (revision 9b3b6a7, branch master"));
+        assert!(description.contains("DOUBLE_SUPPORT"));
+        assert!(description.contains("no exporters"));
+    }
+
+    #[test]
+    fn version_of_the_library() {
+        let version = version::version();
+
+        assert!(version.major >= 5);
+        assert!(version.legal.contains("Assimp"));
+    }
+}